@@ -0,0 +1,29 @@
+/* Generated from src/capi.rs, do not edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer to one part of a day, or NULL if the day or part does
+ * not exist, the input is not UTF-8 or does not parse, or the solver
+ * panics. Empty if the part has no answer. Free with aoc_free. */
+char *aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len);
+
+/* One "day<TAB>title" line per registered day. Free with
+ * aoc_free. */
+char *aoc_titles(void);
+
+/* Frees a string returned by this library. NULL is ignored. */
+void aoc_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,47 @@
+/* Exercises the C interface through the shared object; see Makefile. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void expect(const char *what, char *got, const char *want) {
+    int ok = want == NULL ? got == NULL : got != NULL && strcmp(got, want) == 0;
+    printf("%s %s: %s\n", ok ? "ok  " : "FAIL", what, got ? got : "(null)");
+    failures += !ok;
+    aoc_free(got);
+}
+
+static char *solve_file(uint8_t day, uint8_t part, const char *path) {
+    FILE *f = fopen(path, "rb");
+    if (f == NULL) {
+        perror(path);
+        exit(2);
+    }
+    char buf[1 << 16];
+    size_t len = fread(buf, 1, sizeof buf, f);
+    fclose(f);
+    return aoc_solve(day, part, (const uint8_t *)buf, len);
+}
+
+int main(void) {
+    char *titles = aoc_titles();
+    const char *first = "1\tHistorian Hysteria\n";
+    int ok = titles != NULL && strncmp(titles, first, strlen(first)) == 0;
+    printf("%s titles\n", ok ? "ok  " : "FAIL");
+    failures += !ok;
+    aoc_free(titles);
+
+    expect("day 1 part 1", solve_file(1, 1, "../inputs/01-example.txt"), "11");
+    expect("day 1 part 2", solve_file(1, 2, "../inputs/01-example.txt"), "31");
+    expect("day 17 part 1", solve_file(17, 1, "../inputs/17-example.txt"),
+           "4,6,3,5,6,3,5,2,1,0");
+    expect("day 25 part 2", solve_file(25, 2, "../inputs/25-example.txt"), "");
+    expect("unknown day", aoc_solve(26, 1, NULL, 0), NULL);
+    expect("bad input", aoc_solve(1, 1, (const uint8_t *)"x y", 3), NULL);
+
+    return failures != 0;
+}
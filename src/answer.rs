@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// The result of one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    /// The part has no puzzle, e.g. the second half of day 25, or no
+    /// answer for this input, e.g. an example made for the other part.
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::UInt(v) => write!(f, "{}", v),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "(no answer)"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        Answer::Int(v as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Int(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::UInt(v as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::UInt(v)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Answer::None, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from("co,de,ka,ta").to_string(), "co,de,ka,ta");
+        assert_eq!(Answer::None.to_string(), "(no answer)");
+    }
+
+    #[test]
+    fn structural() {
+        assert_eq!(Answer::from(7u64), Answer::UInt(7));
+        assert_ne!(Answer::from(7i64), Answer::from(7u64));
+        assert_eq!(Answer::from(Some(7u64)), Answer::UInt(7));
+        assert_eq!(Answer::from(None::<u64>), Answer::None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::read_path;
+use crate::Answer;
+use crate::InputError;
+use crate::InputResolver;
+
+/// The outcome of comparing an answer with the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail { expected } => {
+                write!(f, "FAIL (expected {})", expected)
+            }
+            Check::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+/// Expected answers for each day and input variant, read from
+/// `inputs/answers.txt`. Each line holds a day, a variant and the two
+/// parts:
+///
+/// ```text
+/// 06 input 4665 1688
+/// ```
+///
+/// `-` marks a part with no puzzle or no answer for that input, and `?`
+/// one that is not recorded yet.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: HashMap<(u8, String), [Option<String>; 2]>,
+}
+
+impl Answers {
+    pub const FILENAME: &'static str = "answers.txt";
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut entries = HashMap::new();
+        for (i, s) in text.lines().enumerate() {
+            let s = s.trim();
+            if s.is_empty() || s.starts_with('#') {
+                continue;
+            }
+            let v: Vec<&str> = s.split_whitespace().collect();
+            let [day, variant, one, two] = v[..] else {
+                return Err(format!(
+                    "line {}: expected day, variant and two answers",
+                    i + 1
+                ));
+            };
+            let day = day.parse().map_err(|_| {
+                format!("line {}: invalid day {:?}", i + 1, day)
+            })?;
+            let known = |s: &str| (s != "?").then(|| s.to_string());
+            entries
+                .insert((day, variant.to_string()), [known(one), known(two)]);
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn load(resolver: &InputResolver) -> Result<Self, InputError> {
+        let path = resolver.find_file(Self::FILENAME, Self::FILENAME)?;
+        let text = read_path(&path)?;
+        Answers::parse(&text)
+            .map_err(|message| InputError::Malformed { path, message })
+    }
+
+    /// Whether the manifest has a line for this day and variant, even one
+    /// with nothing recorded.
+    pub fn contains(&self, day: u8, variant: &str) -> bool {
+        self.entries.contains_key(&(day, variant.to_string()))
+    }
+
+    pub fn expected(&self, day: u8, variant: &str, part: u8) -> Option<&str> {
+        let parts = self.entries.get(&(day, variant.to_string()))?;
+        parts.get((part as usize).checked_sub(1)?)?.as_deref()
+    }
+
+    pub fn check(
+        &self,
+        day: u8,
+        variant: &str,
+        part: u8,
+        answer: &Answer,
+    ) -> Check {
+        match self.expected(day, variant, part) {
+            None => Check::Missing,
+            Some(expected) if expected == recorded(answer) => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// How an answer is written in the manifest.
+fn recorded(answer: &Answer) -> String {
+    match answer {
+        Answer::None => "-".to_string(),
+        answer => answer.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = concat!(
+        "# day variant one two\n",
+        "17 input 1,5,7,4,1,6,0,3,0 108107574778365\n",
+        "25 input 3264 -\n",
+        "25 example 3 ?\n",
+    );
+
+    #[test]
+    fn check() {
+        let answers = Answers::parse(MANIFEST).unwrap();
+        let answer = Answer::from("1,5,7,4,1,6,0,3,0");
+        assert_eq!(answers.check(17, "input", 1, &answer), Check::Pass);
+        assert_eq!(
+            answers.check(17, "input", 2, &Answer::from(1u64)),
+            Check::Fail {
+                expected: "108107574778365".to_string()
+            }
+        );
+        assert_eq!(answers.check(25, "input", 2, &Answer::None), Check::Pass);
+        assert_eq!(
+            answers.check(25, "example", 2, &Answer::None),
+            Check::Missing
+        );
+        assert_eq!(
+            answers.check(1, "input", 1, &Answer::from(0)),
+            Check::Missing
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let err = Answers::parse("01 input 11\n").unwrap_err();
+        assert_eq!(err, "line 1: expected day, variant and two answers");
+    }
+
+    #[test]
+    fn manifest() {
+        let answers = Answers::load(&InputResolver::new(None)).unwrap();
+        assert_eq!(answers.expected(1, "input", 1), Some("2066446"));
+        assert_eq!(answers.expected(1, "example", 2), Some("31"));
+        assert!(answers.contains(12, "example-oxo"));
+        assert!(!answers.contains(12, "example-none"));
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Summary of repeated timings of one part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty());
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            _ => sorted[n / 2],
+        };
+        let secs: Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance =
+            secs.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    /// Relative change of the median against a previous run, e.g. `0.12`
+    /// for 12% slower.
+    pub fn change_since(&self, previous: &Stats) -> f64 {
+        let old = previous.median.as_secs_f64();
+        let new = self.median.as_secs_f64();
+        match old > 0.0 {
+            true => (new - old) / old,
+            false => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:.1?}  median {:.1?}  mean {:.1?}  stddev {:.1?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+/// Runs `f` `warmup` times untimed, then `iterations` times timed.
+pub fn measure(
+    warmup: usize,
+    iterations: usize,
+    mut f: impl FnMut(),
+) -> Stats {
+    for _ in 0..warmup {
+        f();
+    }
+    let samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let t0 = Instant::now();
+            f();
+            t0.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+/// Benchmark results keyed by day and part, in the text form used by
+/// `aoc bench --save` and `--compare`: one `day part min median mean
+/// stddev` line per part, durations in nanoseconds. Part `0` is the
+/// parse phase.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BenchResults {
+    results: BTreeMap<(u8, u8), Stats>,
+}
+
+impl BenchResults {
+    pub fn insert(&mut self, day: u8, part: u8, stats: Stats) {
+        self.results.insert((day, part), stats);
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Stats> {
+        self.results.get(&(day, part))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut results = BenchResults::default();
+        for (i, s) in text.lines().enumerate() {
+            if s.trim().is_empty() || s.starts_with('#') {
+                continue;
+            }
+            let v: Vec<u64> = s
+                .split_whitespace()
+                .map(|v| v.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let [day, part, min, median, mean, stddev] = v[..] else {
+                return Err(format!("line {}: expected 6 fields", i + 1));
+            };
+            let stats = Stats {
+                min: Duration::from_nanos(min),
+                median: Duration::from_nanos(median),
+                mean: Duration::from_nanos(mean),
+                stddev: Duration::from_nanos(stddev),
+            };
+            results.insert(day as u8, part as u8, stats);
+        }
+        Ok(results)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# day part min median mean stddev\n");
+        for (&(day, part), s) in self.results.iter() {
+            text += &format!(
+                "{} {} {} {} {} {}\n",
+                day,
+                part,
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.stddev.as_nanos()
+            );
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.stddev.as_micros(), 2236);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut results = BenchResults::default();
+        results.insert(6, 2, Stats::from_samples(&[ms(3), ms(5)]));
+        let parsed = BenchResults::parse(&results.to_text()).unwrap();
+        assert_eq!(parsed, results);
+        assert!(BenchResults::parse("6 2 1 2 3\n").is_err());
+    }
+
+    #[test]
+    fn regression() {
+        let old = Stats::from_samples(&[ms(10)]);
+        let new = Stats::from_samples(&[ms(12)]);
+        assert!((new.change_since(&old) - 0.2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Answer;
+
+/// Answers and timings from earlier runs, keyed by day, part and a hash of
+/// everything the answer depends on. Entries written by another crate
+/// version are dropped on load.
+///
+/// The file has one `day part hash time_ns version answer` line per part,
+/// where the answer is `i:`, `u:` or `t:` followed by the value, or `-`
+/// for no answer. Part `0` holds the parse time.
+#[derive(Debug, Default)]
+pub struct Cache {
+    entries: HashMap<(u8, u8, u64), (Answer, Duration)>,
+}
+
+impl Cache {
+    pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    /// `target/aoc-cache.txt` in this crate.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-cache.txt")
+    }
+
+    /// Reads a cache file, treating a missing file as an empty cache and
+    /// skipping lines it cannot read.
+    pub fn load(path: &Path) -> Cache {
+        let mut cache = Cache::default();
+        let Ok(text) = fs::read_to_string(path) else {
+            return cache;
+        };
+        for s in text.lines() {
+            let v: Vec<&str> = s.splitn(6, ' ').collect();
+            let [day, part, hash, time, version, answer] = v[..] else {
+                continue;
+            };
+            if version != Self::VERSION {
+                continue;
+            }
+            let parsed = (|| {
+                let key = (
+                    day.parse().ok()?,
+                    part.parse().ok()?,
+                    u64::from_str_radix(hash, 16).ok()?,
+                );
+                let time = Duration::from_nanos(time.parse().ok()?);
+                Some((key, (decode(answer)?, time)))
+            })();
+            if let Some((key, entry)) = parsed {
+                cache.entries.insert(key, entry);
+            }
+        }
+        cache
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut keys: Vec<_> = self.entries.keys().collect();
+        keys.sort();
+        let mut text = String::new();
+        for key in keys {
+            let (answer, time) = &self.entries[key];
+            text += &format!(
+                "{} {} {:016x} {} {} {}\n",
+                key.0,
+                key.1,
+                key.2,
+                time.as_nanos(),
+                Self::VERSION,
+                encode(answer)
+            );
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)
+    }
+
+    pub fn get(
+        &self,
+        day: u8,
+        part: u8,
+        hash: u64,
+    ) -> Option<&(Answer, Duration)> {
+        self.entries.get(&(day, part, hash))
+    }
+
+    pub fn insert(
+        &mut self,
+        day: u8,
+        part: u8,
+        hash: u64,
+        answer: Answer,
+        time: Duration,
+    ) {
+        self.entries.insert((day, part, hash), (answer, time));
+    }
+}
+
+/// FNV-1a over the input and whatever else selects the answer, such as
+/// the variant and parameters. Unlike `DefaultHasher` it is stable across
+/// builds.
+pub fn input_hash(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        for &b in part.as_bytes().iter().chain([0xff].iter()) {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+fn encode(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(v) => format!("i:{}", v),
+        Answer::UInt(v) => format!("u:{}", v),
+        Answer::Text(s) => format!("t:{}", s),
+        Answer::None => "-".to_string(),
+    }
+}
+
+fn decode(s: &str) -> Option<Answer> {
+    match s.split_once(':') {
+        Some(("i", v)) => v.parse().ok().map(Answer::Int),
+        Some(("u", v)) => v.parse().ok().map(Answer::UInt),
+        Some(("t", v)) => Some(Answer::Text(v.to_string())),
+        _ => (s == "-").then_some(Answer::None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let path = env::temp_dir()
+            .join(format!("aoc-cache-test-{}.txt", process::id()));
+        let mut cache = Cache::default();
+        let time = Duration::from_micros(12);
+        cache.insert(6, 1, 7, Answer::from(-4665), time);
+        cache.insert(17, 1, 7, Answer::from("4,6,3 x"), time);
+        cache.insert(25, 2, 7, Answer::None, time);
+        cache.save(&path).unwrap();
+
+        let loaded = Cache::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.get(6, 1, 7), Some(&(Answer::from(-4665), time)));
+        assert_eq!(loaded.get(17, 1, 7).unwrap().0, Answer::from("4,6,3 x"));
+        assert_eq!(loaded.get(25, 2, 7).unwrap().0, Answer::None);
+        assert_eq!(loaded.get(6, 1, 8), None);
+    }
+
+    #[test]
+    fn stale_version() {
+        let path = env::temp_dir()
+            .join(format!("aoc-cache-stale-{}.txt", process::id()));
+        fs::write(&path, "6 1 0000000000000007 12 0.0.0-old u:1\n").unwrap();
+        let loaded = Cache::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.get(6, 1, 7), None);
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(input_hash(&[]), 0xcbf29ce484222325);
+        assert_ne!(input_hash(&["ab", "c"]), input_hash(&["a", "bc"]));
+    }
+}
@@ -0,0 +1,195 @@
+//! A C interface to the solvers. The library only builds as a shared
+//! object on request:
+//!
+//! ```text
+//! cargo rustc --lib --release --crate-type cdylib
+//! ```
+//!
+//! `capi/aoc.h` declares these functions and is checked against
+//! [`c_header`] by the tests; `capi/test.c` exercises them through the
+//! shared object (`make -C capi test`).
+
+use std::ffi::c_char;
+use std::ffi::CString;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+use std::slice;
+use std::str;
+
+use crate::Answer;
+
+/// The C declarations of this module.
+pub fn c_header() -> String {
+    let functions = [
+        "/* The answer to one part of a day, or NULL if the day or part does\n \
+         * not exist, the input is not UTF-8 or does not parse, or the solver\n \
+         * panics. Empty if the part has no answer. Free with aoc_free. */\n\
+         char *aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, \
+         size_t len);",
+        "/* One \"day<TAB>title\" line per registered day. Free with\n \
+         * aoc_free. */\n\
+         char *aoc_titles(void);",
+        "/* Frees a string returned by this library. NULL is ignored. */\n\
+         void aoc_free(char *s);",
+    ];
+    format!(
+        "/* Generated from src/capi.rs, do not edit. */\n\n\
+         #ifndef AOC_H\n#define AOC_H\n\n\
+         #include <stddef.h>\n#include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {{\n#endif\n\n\
+         {}\n\n\
+         #ifdef __cplusplus\n}}\n#endif\n\n#endif\n",
+        functions.join("\n\n")
+    )
+}
+
+fn owned(s: String) -> *mut c_char {
+    // Solver output never contains NUL, but drop it rather than fail.
+    CString::new(s.replace('\0', "")).unwrap().into_raw()
+}
+
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or be NULL with `len` 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+) -> *mut c_char {
+    let input = match input.is_null() {
+        true => &[],
+        false => slice::from_raw_parts(input, len),
+    };
+    let Some(puzzle) = crate::puzzle(day) else {
+        return ptr::null_mut();
+    };
+    let Ok(input) = str::from_utf8(input) else {
+        return ptr::null_mut();
+    };
+    if !(1..=2).contains(&part) {
+        return ptr::null_mut();
+    }
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+        let settings = puzzle.settings("input", &[]).ok()?;
+        let parsed = puzzle.parse(input).ok()?;
+        Some(puzzle.solve(&parsed, &settings, part))
+    }));
+    match answer {
+        Ok(Some(Answer::None)) => owned(String::new()),
+        Ok(Some(answer)) => owned(answer.to_string()),
+        _ => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_titles() -> *mut c_char {
+    let lines: Vec<String> = crate::puzzles()
+        .iter()
+        .map(|p| format!("{}\t{}\n", p.day(), p.title()))
+        .collect();
+    owned(lines.concat())
+}
+
+/// # Safety
+///
+/// `s` must be NULL or a string returned by this library that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::fs;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::read_example;
+
+    fn solve(day: u8, part: u8, input: &str) -> Option<String> {
+        unsafe {
+            let s = aoc_solve(day, part, input.as_ptr(), input.len());
+            if s.is_null() {
+                return None;
+            }
+            let answer = CStr::from_ptr(s).to_str().unwrap().to_string();
+            aoc_free(s);
+            Some(answer)
+        }
+    }
+
+    #[test]
+    fn solves() {
+        assert_eq!(solve(1, 1, &read_example(1)).as_deref(), Some("11"));
+        assert_eq!(solve(25, 2, &read_example(25)).as_deref(), Some(""));
+        assert_eq!(solve(26, 1, ""), None);
+        assert_eq!(solve(1, 3, &read_example(1)), None);
+        assert_eq!(solve(1, 1, "x y"), None);
+        unsafe {
+            assert!(aoc_solve(1, 1, [0xff].as_ptr(), 1).is_null());
+            aoc_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn titles() {
+        unsafe {
+            let s = aoc_titles();
+            let titles = CStr::from_ptr(s).to_str().unwrap().to_string();
+            aoc_free(s);
+            assert_eq!(titles.lines().count(), 25);
+            assert!(titles.starts_with("1\tHistorian Hysteria\n"));
+        }
+    }
+
+    fn header_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("capi/aoc.h")
+    }
+
+    /// Fails with the differing lines when `capi/aoc.h` no longer matches
+    /// [`c_header`]. Regenerate it with `make -C capi header`.
+    #[test]
+    fn header_up_to_date() {
+        let path = header_path();
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        let expected = c_header();
+        if current == expected {
+            return;
+        }
+        let (old, new): (Vec<_>, Vec<_>) =
+            (current.lines().collect(), expected.lines().collect());
+        let mut diff = String::new();
+        for i in 0..old.len().max(new.len()) {
+            let (a, b) = (old.get(i), new.get(i));
+            if a == b {
+                continue;
+            }
+            if let Some(a) = a {
+                diff += &format!("{:4} - {}\n", i + 1, a);
+            }
+            if let Some(b) = b {
+                diff += &format!("{:4} + {}\n", i + 1, b);
+            }
+        }
+        panic!(
+            "{} is stale, run `make -C capi header`:\n{}",
+            path.display(),
+            diff
+        );
+    }
+
+    /// Writes `capi/aoc.h`. Run by `make -C capi header`.
+    #[test]
+    #[ignore]
+    fn write_header() {
+        fs::write(header_path(), c_header()).unwrap();
+    }
+}
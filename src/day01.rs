@@ -1,37 +1,68 @@
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = (Vec<i32>, Vec<i32>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day01::DAY, input);
     let mut a: Vec<i32> = Vec::new();
     let mut b: Vec<i32> = Vec::new();
-    input.trim().lines().for_each(|s| {
+    for s in input.trim().lines() {
         let row = s
             .split_whitespace()
-            .map(|v| v.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
-        assert!(row.len() == 2);
+            .map(|v| src.number(v))
+            .collect::<Result<Vec<i32>, _>>()?;
+        if row.len() != 2 {
+            return Err(src.error(s, "expected two location IDs"));
+        }
         a.push(row[0]);
         b.push(row[1]);
-    });
-    assert!(a.len() == b.len());
-    (a, b)
+    }
+    Ok((a, b))
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let (mut ca, mut cb) = parse_input(input);
+pub fn part_one((ca, cb): &Input) -> i32 {
+    let mut ca = ca.clone();
+    let mut cb = cb.clone();
     ca.sort_unstable();
     cb.sort_unstable();
     ca.into_iter().zip(cb).map(|(a, b)| (a - b).abs()).sum()
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let (ca, cb) = parse_input(input);
+pub fn part_two((ca, cb): &Input) -> i32 {
     let mut count: HashMap<i32, i32> = HashMap::new();
-    cb.into_iter().for_each(|v| {
+    cb.iter().for_each(|&v| {
         *count.entry(v).or_insert(0) += 1;
     });
     ca.iter().map(|v| v * count.get(v).unwrap_or(&0)).sum()
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,7 +70,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(1);
+        let input = parse_input(&read_example(1)).unwrap();
         assert_eq!(part_one(&input), 11);
         assert_eq!(part_two(&input), 31);
     }
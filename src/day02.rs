@@ -1,8 +1,16 @@
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<Vec<i32>>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day02::DAY, input);
     input
         .trim()
         .lines()
-        .map(|s| s.split_whitespace().map(|v| v.parse().unwrap()).collect())
+        .map(|s| s.split_whitespace().map(|v| src.number(v)).collect())
         .collect()
 }
 
@@ -18,20 +26,39 @@ fn is_safe(row: &[i32], ignore: usize) -> bool {
         && iter.all(|v| (1..=3).contains(&v.abs()))
 }
 
-pub fn part_one(input: &str) -> usize {
-    parse_input(input)
-        .iter()
-        .filter(|row| is_safe(row, row.len()))
-        .count()
+pub fn part_one(input: &Input) -> usize {
+    input.iter().filter(|row| is_safe(row, row.len())).count()
 }
 
-pub fn part_two(input: &str) -> usize {
-    parse_input(input)
+pub fn part_two(input: &Input) -> usize {
+    input
         .iter()
         .filter(|row| (0..=row.len()).any(|ignore| is_safe(row, ignore)))
         .count()
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,7 +66,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(2);
+        let input = parse_input(&read_example(2)).unwrap();
         assert_eq!(part_one(&input), 2);
         assert_eq!(part_two(&input), 4);
     }
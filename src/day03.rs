@@ -1,5 +1,15 @@
 use regex::Regex;
 
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+
+type Input = String;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Ok(input.to_string())
+}
+
 pub fn part_one(input: &str) -> i32 {
     let pattern = r"mul\((\d{1,3},\d{1,3})\)";
     let re = Regex::new(pattern).unwrap();
@@ -39,6 +49,28 @@ pub fn part_two(input: &str) -> i32 {
         .sum()
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,23 @@
-use core::str;
+use std::iter;
 
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    input.trim().lines().map(|s| s.bytes().collect()).collect()
+use crate::Answer;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
+
+type Input = Grid<char>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day04::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty word search"));
+    }
+    Grid::parse(&src, input, Some)
 }
 
-fn build_lps(pattern: &[u8]) -> Vec<usize> {
+fn build_lps(pattern: &[char]) -> Vec<usize> {
     let mut lps = vec![0; pattern.len()];
     let mut length = 0; // length of the previous longest prefix suffix
     let mut i = 1;
@@ -26,7 +39,7 @@ fn build_lps(pattern: &[u8]) -> Vec<usize> {
     lps
 }
 
-fn kmp_search_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+fn kmp_search_all(text: &[char], pattern: &[char]) -> Vec<usize> {
     let lps = build_lps(pattern);
     let mut matches = Vec::new(); // Store all match indices
 
@@ -56,114 +69,81 @@ fn kmp_search_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
     matches
 }
 
-pub fn part_one(input: &str) -> usize {
-    let grid = parse_input(input);
-    let h = grid.len();
-    let w = grid[0].len();
-    let mut occurs = 0;
+/// The letters from `start` to the edge of the grid, one `step` at a
+/// time.
+fn line(grid: &Input, start: Point, step: Point) -> Vec<char> {
+    iter::successors(Some(start), |&p| Some(p + step))
+        .map_while(|p| grid.get(p).copied())
+        .collect()
+}
 
-    fn search(s: &[u8]) -> usize {
-        let a: &[u8] = "XMAS".as_bytes();
-        let b: &[u8] = "SAMX".as_bytes();
-        kmp_search_all(s, a).len() + kmp_search_all(s, b).len()
-    }
+pub fn part_one(grid: &Input) -> usize {
+    let h = grid.height() as i32;
+    let w = grid.width() as i32;
 
-    // horizontal
-    #[allow(clippy::needless_range_loop)]
-    for y in 0..h {
-        let s: Vec<u8> = (0..w).map(|x| grid[y][x]).collect();
-        occurs += search(&s);
-    }
-    // vertical
-    for x in 0..w {
-        let s: Vec<u8> = (0..h).map(|y| grid[y][x]).collect();
-        occurs += search(&s);
-    }
-    // slash
-    for i in 0..w {
-        let mut x = i;
-        let mut y = 0;
-        let mut s: Vec<u8> = Vec::new();
-        while y < h {
-            s.push(grid[y][x]);
-            if x == 0 {
-                break;
-            }
-            x -= 1;
-            y += 1;
-        }
-        occurs += search(&s);
-    }
-    for i in 1..h {
-        let mut x = w - 1;
-        let mut y = i;
-        let mut s: Vec<u8> = Vec::new();
-        while y < h {
-            s.push(grid[y][x]);
-            if x == 0 {
-                break;
-            }
-            x -= 1;
-            y += 1;
-        }
-        occurs += search(&s);
-    }
-    // backslash
-    for i in 0..w {
-        let mut x = i;
-        let mut y = 0;
-        let mut s: Vec<u8> = Vec::new();
-        while y < h && x < w {
-            s.push(grid[y][x]);
-            x += 1;
-            y += 1;
-        }
-        occurs += search(&s);
-    }
-    for i in 1..h {
-        let mut x = 0;
-        let mut y = i;
-        let mut s: Vec<u8> = Vec::new();
-        while y < h && x < w {
-            s.push(grid[y][x]);
-            x += 1;
-            y += 1;
-        }
-        occurs += search(&s);
+    fn search(s: &[char]) -> usize {
+        let a: Vec<char> = "XMAS".chars().collect();
+        let b: Vec<char> = "SAMX".chars().collect();
+        kmp_search_all(s, &a).len() + kmp_search_all(s, &b).len()
     }
 
-    //
-    occurs
+    let (right, down) = (Point::new(1, 0), Point::new(0, 1));
+    let top = (0..w).map(|x| Point::new(x, 0));
+    let left = (0..h).map(|y| Point::new(0, y));
+    let right_edge = (1..h).map(|y| Point::new(w - 1, y));
+    let lines = iter::empty()
+        // horizontal
+        .chain(left.clone().map(|p| (p, right)))
+        // vertical
+        .chain(top.clone().map(|p| (p, down)))
+        // slash
+        .chain(top.clone().chain(right_edge).map(|p| (p, down - right)))
+        // backslash
+        .chain(top.chain(left.skip(1)).map(|p| (p, down + right)));
+    lines
+        .map(|(start, step)| search(&line(grid, start, step)))
+        .sum()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let grid = parse_input(input);
-    let h = grid.len();
-    let w = grid[0].len();
+pub fn part_two(grid: &Input) -> usize {
     // find all possible 'A' positions
     // and validate X-MAS
-    (1..h - 1)
-        .flat_map(|y| {
-            (1..w - 1)
-                .map(|x| (x, y))
-                .filter(|&(x, y)| grid[y][x] == b'A')
-                .collect::<Vec<(usize, usize)>>()
-        })
-        .filter(|(x, y)| {
+    grid.find_all(&'A')
+        .filter(|&p| {
+            let at = |dx, dy| grid.get(p + Point::new(dx, dy));
             matches!(
-                (
-                    (grid[y - 1][x - 1], grid[y + 1][x + 1]),
-                    (grid[y - 1][x + 1], grid[y + 1][x - 1]),
-                ),
-                ((b'M', b'S'), (b'M', b'S'))
-                    | ((b'M', b'S'), (b'S', b'M'))
-                    | ((b'S', b'M'), (b'M', b'S'))
-                    | ((b'S', b'M'), (b'S', b'M'))
+                ((at(-1, -1), at(1, 1)), (at(1, -1), at(-1, 1))),
+                ((Some('M'), Some('S')), (Some('M'), Some('S')))
+                    | ((Some('M'), Some('S')), (Some('S'), Some('M')))
+                    | ((Some('S'), Some('M')), (Some('M'), Some('S')))
+                    | ((Some('S'), Some('M')), (Some('S'), Some('M')))
             )
         })
         .count()
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,7 +151,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(4);
+        let input = parse_input(&read_example(4)).unwrap();
         assert_eq!(part_one(&input), 18);
         assert_eq!(part_two(&input), 9);
     }
@@ -1,37 +1,43 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
-fn parse_input(input: &str) -> (HashSet<(i32, i32)>, Vec<Vec<i32>>) {
-    let i = input.find("\n\n").unwrap();
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = (HashSet<(i32, i32)>, Vec<Vec<i32>>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day05::DAY, input);
+    let i = input
+        .find("\n\n")
+        .ok_or_else(|| src.eof("missing blank line before the updates"))?;
     let rules: HashSet<(i32, i32)> = input[..i]
         .trim()
         .lines()
-        .map(|s| {
-            s.splitn(2, '|')
-                .map(|v| v.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+        .map(|s| match s.split_once('|') {
+            Some((a, b)) => Ok((src.number(a)?, src.number(b)?)),
+            None => Err(src.error(s, "expected a rule like 47|53")),
         })
-        .map(|v| (v[0], v[1]))
-        .collect();
+        .collect::<Result<_, _>>()?;
     let updates: Vec<Vec<i32>> = input[i..]
         .trim()
         .lines()
-        .map(|s| s.split(',').map(|v| v.parse::<i32>().unwrap()).collect())
-        .collect();
-    (rules, updates)
+        .map(|s| s.split(',').map(|v| src.number(v)).collect())
+        .collect::<Result<_, _>>()?;
+    Ok((rules, updates))
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let (rules, updates) = parse_input(input);
+pub fn part_one((rules, updates): &Input) -> i32 {
     updates
         .iter()
         .map(|row| {
-            (0..row.len() - 1)
-                .all(|i| {
-                    (i + 1..row.len())
-                        .map(|j| (row[i], row[j]))
-                        .all(|(a, b)| rules.contains(&(a, b)))
-                })
+            (0..row.len() - 1).all(|i| {
+                (i + 1..row.len())
+                    .map(|j| (row[i], row[j]))
+                    .all(|(a, b)| rules.contains(&(a, b)))
+            })
         })
         .enumerate()
         .filter(|&(_, is_correct)| is_correct)
@@ -40,17 +46,15 @@ pub fn part_one(input: &str) -> i32 {
         .sum()
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let (rules, updates) = parse_input(input);
+pub fn part_two((rules, updates): &Input) -> i32 {
     updates
         .iter()
         .map(|row| {
-            (0..row.len() - 1)
-                .all(|i| {
-                    (i + 1..row.len())
-                        .map(|j| (row[i], row[j]))
-                        .all(|(a, b)| rules.contains(&(a, b)))
-                })
+            (0..row.len() - 1).all(|i| {
+                (i + 1..row.len())
+                    .map(|j| (row[i], row[j]))
+                    .all(|(a, b)| rules.contains(&(a, b)))
+            })
         })
         .enumerate()
         .filter(|&(_, is_correct)| !is_correct)
@@ -66,6 +70,28 @@ pub fn part_two(input: &str) -> i32 {
         .sum()
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,7 +99,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(5);
+        let input = parse_input(&read_example(5)).unwrap();
         assert_eq!(part_one(&input), 143);
         assert_eq!(part_two(&input), 123);
     }
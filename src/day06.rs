@@ -1,63 +1,48 @@
 use std::collections::HashSet;
+use std::mem;
 
-type Pos = (i32, i32);
-const DIRECTIONS: [Pos; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+use crate::Answer;
+use crate::Color;
+use crate::Dir;
+use crate::Frame;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-fn parse_input(input: &str) -> (HashSet<Pos>, i32, i32, Pos) {
-    let mut start = (0, 0);
-    let mut height = 0;
-    let mut width = 0;
-    let obstructions = input
-        .trim()
-        .lines()
-        .enumerate()
-        .filter(|(y, _)| {
-            height = height.max(*y as i32);
-            true
-        })
-        .flat_map(|(y, s)| {
-            s.bytes()
-                .enumerate()
-                .filter(|(x, _)| {
-                    width = width.max(*x as i32);
-                    true
-                })
-                .filter(|&(x, c)| match c {
-                    b'#' => true,
-                    b'^' => {
-                        start = (x as i32, y as i32);
-                        false
-                    }
-                    _ => false,
-                })
-                .map(|(x, _)| (x as i32, y as i32))
-                .collect::<Vec<_>>()
-        })
-        .collect();
-    (obstructions, width + 1, height + 1, start)
+type Input = (Grid<char>, Point);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day06::DAY, input);
+    let map = Grid::parse(&src, input, |c| match c {
+        '.' | '#' | '^' => Some(c),
+        _ => None,
+    })?;
+    let start = map
+        .find(&'^')
+        .ok_or_else(|| src.eof("no guard '^' on the map"))?;
+    Ok((map, start))
 }
 
 fn search(
-    obstructions: &HashSet<Pos>,
-    width: i32,
-    height: i32,
-    pretrace: &[(Pos, usize)],
-) -> (Vec<(Pos, usize)>, bool) {
+    map: &Grid<char>,
+    pretrace: &[(Point, Dir)],
+) -> (Vec<(Point, Dir)>, bool) {
     let (mut p, mut d) = pretrace.last().unwrap();
     let mut trace: Vec<_> = pretrace[..pretrace.len() - 1].to_vec();
     let mut visited: HashSet<_> = trace.iter().cloned().collect();
     let mut is_loop = false;
-    while p.0 >= 0 && p.0 < width && p.1 >= 0 && p.1 < height {
+    while map.contains(p) {
         if visited.contains(&(p, d)) {
             is_loop = true;
             break;
         }
         trace.push((p, d));
         visited.insert((p, d));
-        let dir = DIRECTIONS[d];
-        let q = (p.0 + dir.0, p.1 + dir.1);
-        if obstructions.contains(&q) {
-            d = (d + 1) % 4;
+        let q = p.step(d);
+        if map.get(q) == Some(&'#') {
+            d = d.turn_right();
             continue;
         }
         p = q;
@@ -65,16 +50,17 @@ fn search(
     (trace, is_loop)
 }
 
-pub fn part_one(input: &str) -> usize {
-    let (obstructions, width, height, start) = parse_input(input);
-    let (trace, _) = search(&obstructions, width, height, &[(start, 0)]);
+pub fn part_one(input: &Input) -> usize {
+    let (map, start) = input;
+    let (trace, _) = search(map, &[(*start, Dir::Up)]);
     trace.iter().map(|&(p, _)| p).collect::<HashSet<_>>().len()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let (mut obstructions, width, height, start) = parse_input(input);
+pub fn part_two(input: &Input) -> usize {
+    let (map, start) = input;
+    let mut map = map.clone();
     let mut visited = HashSet::new();
-    let (trace, _) = search(&obstructions, width, height, &[(start, 0)]);
+    let (trace, _) = search(&map, &[(*start, Dir::Up)]);
     trace
         .iter()
         .enumerate()
@@ -83,16 +69,65 @@ pub fn part_two(input: &str) -> usize {
         .filter(|&(i, p)| {
             !visited.contains(&p) && {
                 visited.insert(p);
-                obstructions.insert(p);
-                let (_, is_loop) =
-                    search(&obstructions, width, height, &trace[..i]);
-                obstructions.remove(&p);
+                let tile = mem::replace(&mut map[p], '#');
+                let (_, is_loop) = search(&map, &trace[..i]);
+                map[p] = tile;
                 is_loop
             }
         })
         .count()
 }
 
+/// The guard's patrol one step at a time, with the tiles already visited
+/// marked `X`.
+pub fn frames(input: &Input) -> impl Iterator<Item = Frame> {
+    let (map, start) = input;
+    let (trace, _) = search(map, &[(*start, Dir::Up)]);
+    let mut drawn = map.clone();
+    let steps = trace.len();
+    trace.into_iter().enumerate().map(move |(i, (p, d))| {
+        drawn[p] = d.to_char();
+        let caption = format!("step {}/{}", i + 1, steps);
+        let frame = Frame::new(caption, &drawn, |c| match c {
+            '#' => Color::Gray,
+            'X' => Color::Yellow,
+            '.' => Color::Default,
+            _ => Color::Red,
+        });
+        drawn[p] = 'X';
+        frame
+    })
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+
+    fn frames<'a>(
+        input: &'a Input,
+        _: &'a (),
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        Box::new(frames(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,7 +135,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(6);
+        let input = parse_input(&read_example(6)).unwrap();
         assert_eq!(part_one(&input), 41);
         assert_eq!(part_two(&input), 6);
     }
@@ -1,24 +1,36 @@
-fn parse_input(input: &str) -> Vec<(i64, Vec<i64>)> {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<(i64, Vec<i64>)>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day07::DAY, input);
     input
         .trim()
         .lines()
-        .map(|s| s.splitn(2, ':').map(|s| s.trim()).collect::<Vec<&str>>())
         .map(|s| {
-            (
-                s[0].parse::<i64>().unwrap(),
-                s[1].split_whitespace()
-                    .map(|v| v.parse::<i64>().unwrap())
-                    .collect(),
-            )
+            let (a, b) = s
+                .split_once(':')
+                .ok_or_else(|| src.error(s, "expected a test value"))?;
+            let numbers = b
+                .split_whitespace()
+                .map(|v| src.number(v))
+                .collect::<Result<Vec<i64>, _>>()?;
+            if numbers.is_empty() {
+                return Err(src.error(b, "expected numbers after ':'"));
+            }
+            Ok((src.number(a.trim())?, numbers))
         })
         .collect()
 }
 
 fn total_calibration(
-    input: &str,
+    equations: &Input,
     f: fn(i64, &[i64], i64, usize) -> bool,
 ) -> i64 {
-    parse_input(input)
+    equations
         .iter()
         .filter_map(|(expect, numbers)| {
             match f(*expect, numbers, numbers[0], 1) {
@@ -29,7 +41,7 @@ fn total_calibration(
         .sum()
 }
 
-pub fn part_one(input: &str) -> i64 {
+pub fn part_one(input: &Input) -> i64 {
     fn calc(expect: i64, numbers: &[i64], value: i64, i: usize) -> bool {
         if i >= numbers.len() {
             value == expect
@@ -41,7 +53,7 @@ pub fn part_one(input: &str) -> i64 {
     total_calibration(input, calc)
 }
 
-pub fn part_two(input: &str) -> i64 {
+pub fn part_two(input: &Input) -> i64 {
     fn calc(expect: i64, numbers: &[i64], value: i64, i: usize) -> bool {
         if i >= numbers.len() {
             value == expect
@@ -62,6 +74,28 @@ pub fn part_two(input: &str) -> i64 {
     total_calibration(input, calc)
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,7 +103,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(7);
+        let input = parse_input(&read_example(7)).unwrap();
         assert_eq!(part_one(&input), 3749);
         assert_eq!(part_two(&input), 11387);
     }
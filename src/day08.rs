@@ -1,53 +1,53 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-type Pos = (i32, i32);
-type Antennas = HashMap<char, HashSet<Pos>>;
+use crate::Answer;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-fn parse_input(input: &str) -> (Antennas, i32, i32) {
-    let h = input.trim().lines().count() as i32;
-    let w = input.trim().lines().next().unwrap().len() as i32;
+type Antennas = HashMap<char, HashSet<Point>>;
+
+type Input = (Antennas, Grid<char>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day08::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty antenna map"));
+    }
+    let map = Grid::parse(&src, input, Some)?;
     let mut antennas: Antennas = Antennas::new();
-    input.trim().lines().enumerate().for_each(|(y, s)| {
-        s.chars().enumerate().for_each(|(x, c)| match c {
-            '.' => {}
-            c => {
-                let p = (x as i32, y as i32);
-                antennas
-                    .entry(c)
-                    .and_modify(|v| {
-                        v.insert(p);
-                    })
-                    .or_insert([p].into_iter().collect());
-            }
-        });
-    });
-    (antennas, w, h)
+    for (p, &c) in map.iter().filter(|(_, &c)| c != '.') {
+        antennas.entry(c).or_default().insert(p);
+    }
+    Ok((antennas, map))
 }
 
-pub fn part_one(input: &str) -> usize {
-    let (antennas, w, h) = parse_input(input);
-    let antinodes: HashSet<Pos> = antennas
-        .iter()
-        .flat_map(|(_, nodes)| {
+pub fn part_one(input: &Input) -> usize {
+    let (antennas, map) = input;
+    let antinodes: HashSet<Point> = antennas
+        .values()
+        .flat_map(|nodes| {
             nodes.iter().flat_map(|a| {
                 nodes
                     .iter()
                     .filter_map(|b| match a == b {
                         true => None,
-                        false => Some((b.0 + b.0 - a.0, b.1 + b.1 - a.1)),
+                        false => Some(*b + (*b - *a)),
                     })
-                    .filter(|&(x, y)| x >= 0 && x < w && y >= 0 && y < h)
-                    .collect::<Vec<Pos>>()
+                    .filter(|&p| map.contains(p))
+                    .collect::<Vec<Point>>()
             })
         })
         .collect();
     antinodes.len()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let (antennas, w, h) = parse_input(input);
-    let mut antinodes: HashSet<Pos> =
+pub fn part_two(input: &Input) -> usize {
+    let (antennas, map) = input;
+    let mut antinodes: HashSet<Point> =
         antennas.values().flatten().cloned().collect();
     for (_, nodes) in antennas.iter() {
         for a in nodes.iter() {
@@ -55,11 +55,11 @@ pub fn part_two(input: &str) -> usize {
                 if a == b {
                     continue;
                 }
-                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
-                let (mut x, mut y) = (b.0 + dx, b.1 + dy);
-                while x >= 0 && x < w && y >= 0 && y < h {
-                    antinodes.insert((x, y));
-                    (x, y) = (x + dx, y + dy)
+                let step = *b - *a;
+                let mut p = *b + step;
+                while map.contains(p) {
+                    antinodes.insert(p);
+                    p += step;
                 }
             }
         }
@@ -67,6 +67,28 @@ pub fn part_two(input: &str) -> usize {
     antinodes.len()
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,7 +96,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(8);
+        let input = parse_input(&read_example(8)).unwrap();
         assert_eq!(part_one(&input), 14);
         assert_eq!(part_two(&input), 34);
     }
@@ -1,14 +1,31 @@
-fn parse_input(input: &str) -> Vec<Option<usize>> {
-    input
-        .trim()
-        .chars()
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<usize>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day09::DAY, input);
+    let s = input.trim();
+    if s.is_empty() {
+        return Err(src.eof("empty disk map"));
+    }
+    s.char_indices()
+        .map(|(i, c)| match c.to_digit(10) {
+            Some(n) => Ok(n as usize),
+            None => Err(src.error(&s[i..], format!("unexpected {:?}", c))),
+        })
+        .collect()
+}
+
+fn build_disk(sizes: &[usize]) -> Vec<Option<usize>> {
+    sizes
+        .iter()
         .enumerate()
-        .flat_map(|(i, c)| {
-            let n = c.to_digit(10).unwrap() as usize;
-            match i % 2 {
-                0 => vec![Some(i / 2); n],
-                _ => vec![None; n],
-            }
+        .flat_map(|(i, &n)| match i % 2 {
+            0 => vec![Some(i / 2); n],
+            _ => vec![None; n],
         })
         .collect()
 }
@@ -23,8 +40,8 @@ fn checksum(disk: &[Option<usize>]) -> usize {
         .sum()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut disk = parse_input(input);
+pub fn part_one(input: &Input) -> usize {
+    let mut disk = build_disk(input);
     let mut a = 0;
     let mut b = disk.len() - 1;
     while a < b {
@@ -39,16 +56,14 @@ pub fn part_one(input: &str) -> usize {
     checksum(&disk)
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut disk = parse_input(input);
+pub fn part_two(input: &Input) -> usize {
+    let mut disk = build_disk(input);
     let mut files: Vec<(usize, usize)> = {
         let mut p = 0;
         input
-            .trim()
-            .chars()
+            .iter()
             .enumerate()
-            .filter_map(|(i, c)| {
-                let n = c.to_digit(10).unwrap() as usize;
+            .filter_map(|(i, &n)| {
                 let f = if i % 2 == 0 { Some((p, n)) } else { None };
                 p += n;
                 f
@@ -95,6 +110,28 @@ pub fn part_two(input: &str) -> usize {
     checksum(&disk)
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,7 +149,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(9);
+        let input = parse_input(&read_example(9)).unwrap();
         assert_eq!(part_one(&input), 1928);
         assert_eq!(part_two(&input), 2858);
     }
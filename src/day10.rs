@@ -1,180 +1,146 @@
 use std::collections::HashSet;
 use std::collections::VecDeque;
 
-type Pos = (i16, i16);
-
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    input
-        .trim()
-        .lines()
-        .map(|s| {
-            s.chars()
-                .map(|c| c.to_digit(10).unwrap_or(128) as u8)
-                .collect()
-        })
-        .collect()
+use crate::Answer;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
+
+type Input = Grid<u8>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day10::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty topographic map"));
+    }
+    // Impassable tiles are marked '.' in the examples.
+    Grid::parse(&src, input, |c| Some(c.to_digit(10).unwrap_or(128) as u8))
 }
 
-fn find_trailheads(map: &[Vec<u8>]) -> Vec<Pos> {
-    map.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, v)| **v == 0)
-                .map(|(x, _)| (x as i16, y as i16))
-                .collect::<Vec<_>>()
-        })
-        .collect()
+fn find_trailheads(map: &Input) -> Vec<Point> {
+    map.find_all(&0).collect()
 }
 
-fn find_trailhead_targets(map: &[Vec<u8>], head: Pos) -> Vec<Pos> {
-    const DIRS: [Pos; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-    let h = map.len() as i16;
-    let w = map[0].len() as i16;
+fn find_trailhead_targets(map: &Input, head: Point) -> Vec<Point> {
     let mut targets = Vec::new();
-    let mut queue: VecDeque<Pos> = VecDeque::new();
+    let mut queue: VecDeque<Point> = VecDeque::new();
     queue.push_back(head);
-    while let Some((x, y)) = queue.pop_front() {
-        let v1 = map[y as usize][x as usize];
+    while let Some(p) = queue.pop_front() {
+        let v1 = map[p];
         if v1 == 9 {
-            targets.push((x, y));
+            targets.push(p);
             continue;
         }
-        for (dx, dy) in DIRS {
-            let (x, y) = (x + dx, y + dy);
-            if x < 0 || x >= w || y < 0 || y >= h {
-                continue;
-            }
-            let v2 = map[y as usize][x as usize];
-            if v1 + 1 != v2 {
-                continue;
-            }
-            queue.push_back((x, y));
-        }
+        queue.extend(map.neighbours(p).filter(|&q| map[q] == v1 + 1));
     }
     targets
 }
 
-fn find_trailhead_score(map: &[Vec<u8>], head: Pos) -> usize {
+fn find_trailhead_score(map: &Input, head: Point) -> usize {
     find_trailhead_targets(map, head)
         .into_iter()
         .collect::<HashSet<_>>()
         .len()
 }
 
-fn find_trailhead_rating(map: &[Vec<u8>], head: Pos) -> usize {
+fn find_trailhead_rating(map: &Input, head: Point) -> usize {
     find_trailhead_targets(map, head).len()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let map = parse_input(input);
-    let heads = find_trailheads(&map);
+pub fn part_one(map: &Input) -> usize {
+    let heads = find_trailheads(map);
     heads
         .into_iter()
-        .map(|head| find_trailhead_score(&map, head))
+        .map(|head| find_trailhead_score(map, head))
         .sum()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let map = parse_input(input);
-    let heads = find_trailheads(&map);
+pub fn part_two(map: &Input) -> usize {
+    let heads = find_trailheads(map);
     heads
         .into_iter()
-        .map(|head| find_trailhead_rating(&map, head))
+        .map(|head| find_trailhead_rating(map, head))
         .sum()
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::read_named_example;
 
-    #[rustfmt::skip]
-    const INPUT_1: &str = concat!(
-        "0123\n",
-        "1234\n",
-        "8765\n",
-        "9876\n"
-    );
-
-    #[rustfmt::skip]
-    const INPUT_2: &str = concat!(
-        "...0...\n",
-        "...1...\n",
-        "...2...\n",
-        "6543456\n",
-        "7.....7\n",
-        "8.....8\n",
-        "9.....9\n"
-    );
-
-    #[rustfmt::skip]
-    const INPUT_3: &str = concat!(
-        "..90..9\n",
-        "...1.98\n",
-        "...2..7\n",
-        "6543456\n",
-        "765.987\n",
-        "876....\n",
-        "987....\n",
-    );
-
-    #[rustfmt::skip]
-    const INPUT_4: &str = concat!(
-        "10..9..\n",
-        "2...8..\n",
-        "3...7..\n",
-        "4567654\n",
-        "...8..3\n",
-        "...9..2\n",
-        ".....01\n",
-    );
+    fn named(name: &str) -> Input {
+        parse_input(&read_named_example(10, name)).unwrap()
+    }
 
     #[test]
     fn example_find_trailheads() {
-        assert_eq!(find_trailheads(&parse_input(INPUT_1)).len(), 1);
-        assert_eq!(find_trailheads(&parse_input(INPUT_2)).len(), 1);
-        assert_eq!(find_trailheads(&parse_input(INPUT_3)).len(), 1);
-        assert_eq!(find_trailheads(&parse_input(INPUT_4)).len(), 2);
+        assert_eq!(find_trailheads(&named("square")).len(), 1);
+        assert_eq!(find_trailheads(&named("fork")).len(), 1);
+        assert_eq!(find_trailheads(&named("four")).len(), 1);
+        assert_eq!(find_trailheads(&named("pair")).len(), 2);
 
         let input = read_example(10);
-        let map = parse_input(&input);
+        let map = parse_input(&input).unwrap();
         let heads = find_trailheads(&map);
         assert_eq!(heads.len(), 9);
     }
 
     #[test]
     fn example_find_trailheads_score_1() {
-        let map = parse_input(INPUT_1);
+        let map = named("square");
         let heads = find_trailheads(&map);
         assert_eq!(find_trailhead_score(&map, heads[0]), 1);
     }
 
     #[test]
     fn example_find_trailheads_score_2() {
-        let map = parse_input(INPUT_2);
+        let map = named("fork");
         let heads = find_trailheads(&map);
         assert_eq!(find_trailhead_score(&map, heads[0]), 2);
     }
 
     #[test]
     fn example_find_trailheads_score_3() {
-        let map = parse_input(INPUT_3);
+        let map = named("four");
         let heads = find_trailheads(&map);
         assert_eq!(find_trailhead_score(&map, heads[0]), 4);
     }
 
     #[test]
     fn example_find_trailheads_score_4() {
-        let map = parse_input(INPUT_4);
-        assert_eq!(find_trailhead_score(&map, (1, 0)), 1);
-        assert_eq!(find_trailhead_score(&map, (5, 6)), 2);
+        let map = named("pair");
+        assert_eq!(find_trailhead_score(&map, Point::new(1, 0)), 1);
+        assert_eq!(find_trailhead_score(&map, Point::new(5, 6)), 2);
     }
 
     #[test]
     fn example() {
-        let input = read_example(10);
+        let input = parse_input(&read_example(10)).unwrap();
         assert_eq!(part_one(&input), 36);
         assert_eq!(part_two(&input), 81);
     }
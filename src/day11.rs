@@ -1,18 +1,23 @@
 use std::collections::HashMap;
 
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
 type Cache = HashMap<(u64, u8), usize>;
 
-fn parse_input(input: &str) -> Vec<u64> {
-    input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect()
+type Input = Vec<u64>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day11::DAY, input);
+    input.split_whitespace().map(|s| src.number(s)).collect()
 }
 
 fn split_even_digits(v: u64) -> Option<(u64, u64)> {
     let s = v.to_string();
     let n = s.len();
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         let m = n / 2;
         let a = s[..m].parse().unwrap();
         let b = s[m..].parse().unwrap();
@@ -62,17 +67,38 @@ fn blink_times(stones: &[u64], times: u8) -> usize {
         .sum()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut stones = parse_input(input);
+pub fn part_one(input: &Input) -> usize {
+    let mut stones = input.clone();
     for _ in 0..25 {
         blink_stones(&mut stones);
     }
     stones.len()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let stones = parse_input(input);
-    blink_times(&stones, 75)
+pub fn part_two(stones: &Input) -> usize {
+    blink_times(stones, 75)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -97,9 +123,8 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(11);
-        assert_eq!(part_one(&input), 55312);
-        let stones = parse_input(&input);
+        let stones = parse_input(&read_example(11)).unwrap();
+        assert_eq!(part_one(&stones), 55312);
         assert_eq!(blink_times(&stones, 25), 55312);
     }
 }
@@ -1,87 +1,81 @@
 use std::collections::HashSet;
 use std::collections::VecDeque;
 
-type Grid = Vec<Vec<char>>;
-type Pos = (i32, i32);
-const DIRS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.trim().lines().map(|s| s.chars().collect()).collect()
+use crate::distinct;
+use crate::Answer;
+use crate::Grid;
+use crate::Image;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
+
+type Input = Grid<char>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day12::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty garden map"));
+    }
+    Grid::parse(&src, input, Some)
 }
 
-fn flood_region(grid: &Grid, x: i32, y: i32) -> HashSet<Pos> {
-    let h = grid.len() as i32;
-    let w = grid[0].len() as i32;
-    let mut region: HashSet<Pos> = HashSet::new();
-    let ch = grid[y as usize][x as usize];
-    let mut queue: VecDeque<Pos> = VecDeque::from([(x, y)]);
-    while let Some((x, y)) = queue.pop_front() {
-        if !region.insert((x, y)) {
+fn flood_region(grid: &Input, start: Point) -> HashSet<Point> {
+    let mut region: HashSet<Point> = HashSet::new();
+    let ch = grid[start];
+    let mut queue: VecDeque<Point> = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        if !region.insert(p) {
             continue;
         }
-        for (dx, dy) in DIRS {
-            let (x, y) = (x + dx, y + dy);
-            if x < 0 || x >= w || y < 0 || y >= h {
-                continue;
-            }
-            if ch != grid[y as usize][x as usize] {
-                continue;
-            }
-            if region.contains(&(x, y)) {
-                continue;
-            }
-            queue.push_back((x, y));
-        }
+        queue.extend(
+            grid.neighbours(p)
+                .filter(|&q| grid[q] == ch && !region.contains(&q)),
+        );
     }
     region
 }
 
-fn find_regions(grid: &Grid) -> Vec<HashSet<Pos>> {
-    let h = grid.len() as i32;
-    let w = grid[0].len() as i32;
+fn find_regions(grid: &Input) -> Vec<HashSet<Point>> {
     let mut regions = Vec::new();
-    let mut visited: HashSet<Pos> = HashSet::new();
-    for y in 0..h {
-        for x in 0..w {
-            if visited.contains(&(x, y)) {
-                continue;
-            }
-            let region = flood_region(grid, x, y);
-            visited.extend(region.iter().cloned());
-            regions.push(region);
+    let mut visited: HashSet<Point> = HashSet::new();
+    for p in grid.positions() {
+        if visited.contains(&p) {
+            continue;
         }
+        let region = flood_region(grid, p);
+        visited.extend(region.iter().cloned());
+        regions.push(region);
     }
     regions
 }
 
-fn region_perimeter(region: &HashSet<Pos>) -> usize {
+fn region_perimeter(region: &HashSet<Point>) -> usize {
     let connected_edges: usize = region
         .iter()
-        .map(|(x, y)| {
-            DIRS.map(|(dx, dy)| region.contains(&(x + dx, y + dy)))
-                .into_iter()
-                .filter(|&v| v)
-                .count()
-        })
+        .map(|p| p.neighbours().filter(|q| region.contains(q)).count())
         .sum();
     region.len() * 4 - connected_edges
 }
 
-fn region_sides(region: &HashSet<Pos>) -> usize {
+fn region_sides(region: &HashSet<Point>) -> usize {
     // https://www.youtube.com/watch?v=KXwKGWSQvS0
-    let region: HashSet<Pos> =
-        region.iter().map(|(x, y)| (x * 2 + 1, y * 2 + 1)).collect();
-    const CORNERS: [(i32, i32); 4] = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
-
-    let corners: HashSet<Pos> = region
+    let region: HashSet<Point> =
+        region.iter().map(|&p| p * 2 + Point::new(1, 1)).collect();
+    const CORNERS: [Point; 4] = [
+        Point::new(-1, -1),
+        Point::new(-1, 1),
+        Point::new(1, 1),
+        Point::new(1, -1),
+    ];
+
+    let corners: HashSet<Point> = region
         .iter()
-        .flat_map(|(x, y)| CORNERS.map(|(dx, dy)| (x + dx, y + dy)))
+        .flat_map(|&p| CORNERS.map(|d| p + d))
         .collect();
     corners
         .iter()
-        .map(|(x, y)| {
-            CORNERS.map(|(dx, dy)| region.contains(&(x + dx, y + dy)))
-        })
+        .map(|&p| CORNERS.map(|d| region.contains(&(p + d))))
         .map(|v| match v.iter().filter(|&&v| v).count() {
             1 | 3 => 1,
             2 if v[0] == v[2] => 2,
@@ -90,107 +84,117 @@ fn region_sides(region: &HashSet<Pos>) -> usize {
         .sum()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let grid = parse_input(input);
-    find_regions(&grid)
+pub fn part_one(grid: &Input) -> usize {
+    find_regions(grid)
         .into_iter()
         .map(|region| region.len() * region_perimeter(&region))
         .sum()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let grid = parse_input(input);
-    find_regions(&grid)
+pub fn part_two(grid: &Input) -> usize {
+    find_regions(grid)
         .into_iter()
         .map(|region| region.len() * region_sides(&region))
         .sum()
 }
 
+/// The garden with each region in its own colour.
+pub fn image(grid: &Input) -> Image {
+    let mut regions = Grid::new(grid.width(), grid.height(), 0);
+    for (i, region) in find_regions(grid).into_iter().enumerate() {
+        for p in region {
+            regions[p] = i;
+        }
+    }
+    Image::rgb(&regions, |&i| distinct(i))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+
+    fn image(input: &Input, _: &()) -> Option<Image> {
+        Some(image(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::read_named_example;
 
     #[test]
     fn example_1() {
-        #[rustfmt::skip]
-        const INPUT: &str =
-            concat!(
-                "OOOOO\n",
-                "OXOXO\n",
-                "OOOOO\n",
-                "OXOXO\n",
-                "OOOOO\n"
-            );
-        let grid = parse_input(INPUT);
-
-        let region = flood_region(&grid, 0, 0);
+        let grid = parse_input(&read_named_example(12, "oxo")).unwrap();
+
+        let region = flood_region(&grid, Point::new(0, 0));
         assert_eq!(region.len(), 21);
         assert_eq!(region_perimeter(&region), 36);
 
-        let region = flood_region(&grid, 1, 1);
+        let region = flood_region(&grid, Point::new(1, 1));
         assert_eq!(region.len(), 1);
         assert_eq!(region_perimeter(&region), 4);
     }
 
     #[test]
     fn example_2() {
-        #[rustfmt::skip]
-        const INPUT: &str =
-            concat!(
-                "OOOOO\n",
-                "OXOXO\n",
-                "OOOOO\n",
-                "OXOXO\n",
-                "OOOOO\n"
-            );
-        let grid = parse_input(INPUT);
-
-        let region = flood_region(&grid, 0, 0);
+        let grid = parse_input(&read_named_example(12, "oxo")).unwrap();
+
+        let region = flood_region(&grid, Point::new(0, 0));
         assert_eq!(region.len(), 21);
         assert_eq!(region_perimeter(&region), 36);
 
-        let region = flood_region(&grid, 1, 1);
+        let region = flood_region(&grid, Point::new(1, 1));
         assert_eq!(region.len(), 1);
         assert_eq!(region_perimeter(&region), 4);
     }
 
     #[test]
     fn example_3() {
-        #[rustfmt::skip]
-        const INPUT: &str =
-            concat!(
-                "AAAA\n",
-                "BBCD\n",
-                "BBCC\n",
-                "EEEC\n",
-            );
-        let grid = parse_input(INPUT);
+        let grid = parse_input(&read_named_example(12, "small")).unwrap();
         // A
-        let region = flood_region(&grid, 0, 0);
+        let region = flood_region(&grid, Point::new(0, 0));
         assert_eq!(region.len(), 4);
         assert_eq!(region_sides(&region), 4);
         // B
-        let region = flood_region(&grid, 0, 1);
+        let region = flood_region(&grid, Point::new(0, 1));
         assert_eq!(region.len(), 4);
         assert_eq!(region_sides(&region), 4);
         // C
-        let region = flood_region(&grid, 2, 1);
+        let region = flood_region(&grid, Point::new(2, 1));
         assert_eq!(region.len(), 4);
         assert_eq!(region_sides(&region), 8);
         // D
-        let region = flood_region(&grid, 3, 1);
+        let region = flood_region(&grid, Point::new(3, 1));
         assert_eq!(region.len(), 1);
         assert_eq!(region_sides(&region), 4);
         // E
-        let region = flood_region(&grid, 0, 3);
+        let region = flood_region(&grid, Point::new(0, 3));
         assert_eq!(region.len(), 3);
         assert_eq!(region_sides(&region), 4);
     }
 
     #[test]
     fn example() {
-        let input = read_example(12);
+        let input = parse_input(&read_example(12)).unwrap();
         assert_eq!(part_one(&input), 1930);
         assert_eq!(part_two(&input), 1206);
     }
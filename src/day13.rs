@@ -1,22 +1,36 @@
-fn parse_input(input: &str) -> Vec<Vec<Vec<i64>>> {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<Vec<Vec<i64>>>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day13::DAY, input);
     input
         .split("\n\n")
         .map(|section| {
-            section
+            let config = section
                 .lines()
                 .map(|s| {
-                    s.splitn(2, ':')
-                        .last()
-                        .unwrap()
-                        .trim()
+                    s.split_once(':')
+                        .ok_or_else(|| src.error(s, "expected ':'"))?
+                        .1
                         .splitn(2, ',')
                         .map(|s| s.trim())
-                        .map(|s| s[2..].parse::<i64>().unwrap())
-                        .collect::<Vec<_>>()
+                        .map(|s| match s.get(2..) {
+                            Some(v) => src.number(v),
+                            None => Err(src.error(s, "expected X+n or Y=n")),
+                        })
+                        .collect::<Result<Vec<_>, _>>()
                 })
-                .collect::<Vec<_>>()
+                .collect::<Result<Vec<_>, _>>()?;
+            if config.len() != 3 || config.iter().any(|v| v.len() != 2) {
+                return Err(src.error(section, "expected A, B and Prize"));
+            }
+            Ok(config)
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 /// claude.ai
@@ -81,8 +95,7 @@ fn solve_two_variable_linear_equations(
     }
 }
 
-pub fn part_one(input: &str) -> i64 {
-    let configs = parse_input(input);
+pub fn part_one(configs: &Input) -> i64 {
     configs
         .iter()
         .filter_map(|config| {
@@ -94,8 +107,7 @@ pub fn part_one(input: &str) -> i64 {
         .sum()
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let configs = parse_input(input);
+pub fn part_two(configs: &Input) -> i64 {
     configs
         .iter()
         .filter_map(|config| {
@@ -109,6 +121,28 @@ pub fn part_two(input: &str) -> i64 {
         .sum()
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,7 +150,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(13);
+        let input = parse_input(&read_example(13)).unwrap();
         assert_eq!(part_one(&input), 480);
     }
 }
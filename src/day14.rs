@@ -1,39 +1,94 @@
 use std::collections::HashSet;
 
-type Position = (i32, i32);
-type Velocity = (i32, i32);
-type Robot = (Position, Velocity);
+use crate::params;
+use crate::Answer;
+use crate::Color;
+use crate::Frame;
+use crate::Grid;
+use crate::Image;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-fn parse_input(input: &str) -> Vec<Robot> {
+pub type Position = Point;
+pub type Velocity = Point;
+pub type Robot = (Position, Velocity);
+
+type Input = Vec<Robot>;
+
+/// The size of the room and how long part one waits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub grid: (i32, i32),
+    pub seconds: i32,
+}
+
+impl crate::Params for Params {
+    fn example() -> Self {
+        Params {
+            grid: (11, 7),
+            seconds: 100,
+        }
+    }
+
+    fn input() -> Self {
+        Params {
+            grid: (101, 103),
+            seconds: 100,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "grid" => {
+                self.grid = params::pair(key, value, 'x')?;
+                if self.grid.0 <= 0 || self.grid.1 <= 0 {
+                    return Err(params::invalid(
+                        key,
+                        value,
+                        "a positive size",
+                    ));
+                }
+            }
+            "seconds" => {
+                self.seconds = params::value(key, value)?;
+                if self.seconds < 0 {
+                    return Err(params::invalid(key, value, "a count"));
+                }
+            }
+            _ => return Err(params::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day14::DAY, input);
+    let pair = |s: &str| -> Result<Point, ParseError> {
+        let (x, y) = s
+            .get(2..)
+            .and_then(|s| s.split_once(','))
+            .ok_or_else(|| src.error(s, "expected a pair like p=0,4"))?;
+        Ok(Point::new(src.number(x)?, src.number(y)?))
+    };
     input
         .trim()
         .lines()
-        .map(|s| {
-            s.splitn(2, ' ')
-                .map(|s| {
-                    s[2..]
-                        .splitn(2, ',')
-                        .map(|s| s.parse::<_>().unwrap())
-                        .collect::<Vec<_>>()
-                })
-                .map(|v| (v[0], v[1]))
-                .collect::<Vec<_>>()
+        .map(|s| match s.split_once(' ') {
+            Some((p, v)) => Ok((pair(p)?, pair(v)?)),
+            None => Err(src.error(s, "expected a position and velocity")),
         })
-        .map(|v| (v[0], v[1]))
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 fn move_robot(robot: &Robot, w: i32, h: i32, s: i32) -> Position {
-    let ((mut x, mut y), (dx, dy)) = robot;
-    x = (x + dx * s) % w;
-    y = (y + dy * s) % h;
-    if x < 0 {
-        x += w
-    }
-    if y < 0 {
-        y += h
-    }
-    (x, y)
+    let (p, v) = robot;
+    // In i64 so that a large velocity or number of seconds cannot overflow.
+    let wrap = |p: i32, v: i32, size: i32| {
+        (p as i64 + v as i64 * s as i64).rem_euclid(size as i64) as i32
+    };
+    Point::new(wrap(p.x, v.x, w), wrap(p.y, v.y, h))
 }
 
 fn move_robots(robots: &[Robot], w: i32, h: i32, s: i32) -> Vec<Position> {
@@ -48,7 +103,7 @@ fn quadrants(positions: &[Position], w: i32, h: i32) -> [usize; 4] {
     let wh = h / 2;
     positions
         .iter()
-        .map(|&(x, y)| {
+        .map(|&Point { x, y }| {
             if x < wm && y < wh {
                 [1, 0, 0, 0]
             } else if x > wm && y < wh {
@@ -66,33 +121,29 @@ fn quadrants(positions: &[Position], w: i32, h: i32) -> [usize; 4] {
         })
 }
 
-fn safety_factor(input: &str, w: i32, h: i32, s: i32) -> usize {
-    let robots = parse_input(input);
-    let positions = move_robots(&robots, w, h, s);
+pub fn safety_factor(robots: &[Robot], w: i32, h: i32, s: i32) -> usize {
+    let positions = move_robots(robots, w, h, s);
     quadrants(&positions, w, h).iter().product()
 }
 
-pub fn part_one(input: &str) -> usize {
-    safety_factor(input, 101, 103, 100)
+pub fn part_one(robots: &Input, params: &Params) -> usize {
+    let (w, h) = params.grid;
+    safety_factor(robots, w, h, params.seconds)
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let robots = parse_input(input);
-    let (w, h) = (101, 103);
+pub fn part_two(robots: &Input, params: &Params) -> i32 {
+    let (w, h) = params.grid;
     // if there's a picture. most robots should be connected to show it.
     // so we calculate the count of connected robot each second. and find
     // the max one.
     (0..w * h)
-        .map(|s| move_robots(&robots, w, h, s))
+        .map(|s| move_robots(robots, w, h, s))
         .map(|positions| positions.into_iter().collect::<HashSet<Position>>())
         .map(|positions| {
             positions
                 .iter()
-                .map(|&(x, y)| {
-                    [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
-                        .into_iter()
-                        .filter(|p| positions.contains(p))
-                        .count()
+                .map(|p| {
+                    p.neighbours().filter(|q| positions.contains(q)).count()
                 })
                 .sum::<usize>()
         })
@@ -102,14 +153,115 @@ pub fn part_two(input: &str) -> i32 {
         .0 as i32
 }
 
+/// The room with each tile showing how many robots are on it.
+fn draw(positions: &[Position], w: i32, h: i32) -> Grid<char> {
+    let mut room = Grid::new(w as usize, h as usize, '.');
+    for &p in positions {
+        room[p] = match room[p] {
+            '.' => '1',
+            '9' => '9',
+            c => (c as u8 + 1) as char,
+        };
+    }
+    room
+}
+
+/// The swarm for each second up to the one part one asks about, then the
+/// picture part two finds.
+pub fn frames<'a>(
+    robots: &'a Input,
+    params: &'a Params,
+) -> impl Iterator<Item = Frame> + 'a {
+    let (w, h) = params.grid;
+    let tree = part_two(robots, params);
+    (0..=params.seconds).chain([tree]).map(move |s| {
+        let room = draw(&move_robots(robots, w, h, s), w, h);
+        let caption = match s == tree {
+            true => format!("second {} (tree)", s),
+            false => format!("second {}", s),
+        };
+        Frame::new(caption, &room, |c| match c {
+            '.' => Color::Gray,
+            _ => Color::Green,
+        })
+    })
+}
+
+/// The Christmas tree part two finds, robots in green on black.
+pub fn image(robots: &Input, params: &Params) -> Image {
+    let (w, h) = params.grid;
+    let tree = part_two(robots, params);
+    let room = draw(&move_robots(robots, w, h, tree), w, h);
+    Image::rgb(&room, |&c| match c {
+        '.' => [0, 0, 0],
+        _ => [40, 200, 60],
+    })
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+
+    type Input = Input;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, params: &Params) -> Answer {
+        part_one(input, params).into()
+    }
+
+    fn part_two(input: &Input, params: &Params) -> Answer {
+        part_two(input, params).into()
+    }
+
+    fn frames<'a>(
+        input: &'a Input,
+        params: &'a Params,
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        Box::new(frames(input, params))
+    }
+
+    fn image(input: &Input, params: &Params) -> Option<Image> {
+        Some(image(input, params))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::Params as _;
 
     #[test]
     fn example() {
-        let input = read_example(14);
+        let input = parse_input(&read_example(14)).unwrap();
         assert_eq!(safety_factor(&input, 11, 7, 100), 12);
+        assert_eq!(part_one(&input, &Params::example()), 12);
+        // The robots are back where they started after 11 * 7 seconds.
+        let robot = input[0];
+        let s = 11 * 7 * 27_000_000;
+        assert_eq!(move_robot(&robot, 11, 7, s), robot.0);
+    }
+
+    #[test]
+    fn params() {
+        let overrides = [("grid".to_string(), "5x3".to_string())];
+        let params = Params::resolve("input", &overrides).unwrap();
+        assert_eq!(params.grid, (5, 3));
+        assert_eq!(params.seconds, 100);
+        assert!(
+            Params::resolve("input", &[("size".into(), "1".into())]).is_err()
+        );
+        for (key, value) in
+            [("grid", "-5x3"), ("grid", "0x0"), ("seconds", "-1")]
+        {
+            let overrides = [(key.to_string(), value.to_string())];
+            assert!(Params::resolve("input", &overrides).is_err());
+        }
     }
 }
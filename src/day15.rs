@@ -1,22 +1,27 @@
 use std::collections::HashMap;
 
-const DIRS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+use crate::Answer;
+use crate::Color;
+use crate::Dir;
+use crate::Frame;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-type Pos = (i32, i32);
-
-#[derive(Debug)]
-struct Map {
-    robot: Pos,
-    tiles: HashMap<Pos, char>,
-    w: i32,
-    h: i32,
+/// The warehouse with the robot's tile left empty.
+#[derive(Debug, Clone)]
+pub struct Map {
+    robot: Point,
+    tiles: Grid<char>,
 }
 
 impl Map {
-    fn find_empty(&self, p: Pos, dir: usize) -> Option<Pos> {
-        let mut next = (p.0 + DIRS[dir].0, p.1 + DIRS[dir].1);
-        while self.tiles.get(&next) == Some(&'O') {
-            next = (next.0 + DIRS[dir].0, next.1 + DIRS[dir].1);
+    fn find_empty(&self, p: Point, dir: Dir) -> Option<Point> {
+        let mut next = p.step(dir);
+        while self.tiles.get(next) == Some(&'O') {
+            next = next.step(dir);
         }
         if self.is_empty(next) {
             Some(next)
@@ -25,22 +30,17 @@ impl Map {
         }
     }
 
-    fn is_empty(&self, p: Pos) -> bool {
-        self.robot != p
-            && !self.tiles.contains_key(&p)
-            && p.0 >= 0
-            && p.0 < self.w
-            && p.1 >= 0
-            && p.1 < self.h
+    fn is_empty(&self, p: Point) -> bool {
+        self.robot != p && self.tiles.get(p) == Some(&'.')
     }
 
-    fn move_robot(&mut self, dir: usize) {
-        let next = (self.robot.0 + DIRS[dir].0, self.robot.1 + DIRS[dir].1);
+    fn move_robot(&mut self, dir: Dir) {
+        let next = self.robot.step(dir);
         if self.is_empty(next) {
             self.robot = next;
         } else if let Some(empty) = self.find_empty(self.robot, dir) {
-            self.tiles.remove(&next);
-            self.tiles.insert(empty, 'O');
+            self.tiles[next] = '.';
+            self.tiles[empty] = 'O';
             self.robot = next;
         }
     }
@@ -48,28 +48,23 @@ impl Map {
 
 #[derive(Debug)]
 struct LargeMap {
-    robot: Pos,
-    tiles: HashMap<Pos, char>,
-    w: i32,
-    h: i32,
+    robot: Point,
+    tiles: Grid<char>,
 }
 
 impl LargeMap {
     fn affected_boxes(
         &self,
-        p: Pos,
-        dir: usize,
-        boxes: &mut HashMap<Pos, char>,
+        p: Point,
+        dir: Dir,
+        boxes: &mut HashMap<Point, char>,
     ) -> bool {
-        let next = (p.0 + DIRS[dir].0, p.1 + DIRS[dir].1);
-        if next.0 < 0 || next.0 >= self.w || next.1 < 0 || next.1 >= self.h {
-            return false;
-        }
-        match self.tiles.get(&next) {
+        let next = p.step(dir);
+        match self.tiles.get(next) {
             Some('[') => {
                 boxes.insert(next, '[');
-                if dir != 3 {
-                    let next_pair = (next.0 + 1, next.1);
+                if dir != Dir::Left {
+                    let next_pair = next.step(Dir::Right);
                     boxes.insert(next_pair, ']');
                     self.affected_boxes(next, dir, boxes)
                         && self.affected_boxes(next_pair, dir, boxes)
@@ -79,8 +74,8 @@ impl LargeMap {
             }
             Some(']') => {
                 boxes.insert(next, ']');
-                if dir != 1 {
-                    let next_pair = (next.0 - 1, next.1);
+                if dir != Dir::Right {
+                    let next_pair = next.step(Dir::Left);
                     boxes.insert(next_pair, '[');
                     self.affected_boxes(next, dir, boxes)
                         && self.affected_boxes(next_pair, dir, boxes)
@@ -88,177 +83,184 @@ impl LargeMap {
                     self.affected_boxes(next, dir, boxes)
                 }
             }
-            Some('#') => false,
-            _ => true,
+            Some('.') => true,
+            _ => false,
         }
     }
 
-    fn move_robot(&mut self, dir: usize) {
+    fn draw(&self) -> Grid<char> {
+        let mut tiles = self.tiles.clone();
+        tiles[self.robot] = '@';
+        tiles
+    }
+
+    fn move_robot(&mut self, dir: Dir) {
         let mut boxes = HashMap::new();
         if self.affected_boxes(self.robot, dir, &mut boxes) {
-            self.robot =
-                (self.robot.0 + DIRS[dir].0, self.robot.1 + DIRS[dir].1);
-            boxes.keys().for_each(|p| {
-                self.tiles.remove(p);
-            });
+            self.robot = self.robot.step(dir);
+            boxes.keys().for_each(|&p| self.tiles[p] = '.');
             boxes
                 .into_iter()
-                .map(|(p, ch)| ((p.0 + DIRS[dir].0, p.1 + DIRS[dir].1), ch))
-                .for_each(|(p, ch)| {
-                    self.tiles.insert(p, ch);
-                })
+                .map(|(p, ch)| (p.step(dir), ch))
+                .for_each(|(p, ch)| self.tiles[p] = ch)
         }
     }
-
-    // fn print(&self) {
-    //     for y in 0..self.h {
-    //         for x in 0..self.w {
-    //             let ch = match self.tiles.get(&(x, y)) {
-    //                 Some(ch) => *ch,
-    //                 None if (x, y) == self.robot => '@',
-    //                 None => '.',
-    //             };
-    //             print!("{}", ch);
-    //         }
-    //         println!();
-    //     }
-    // }
 }
 
-fn parse_input(input: &str) -> (Map, Vec<usize>) {
-    let sections: Vec<&str> = input.trim().splitn(2, "\n\n").collect();
+type Input = (Map, Vec<Dir>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day15::DAY, input);
+    let (section, moves) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| src.eof("missing blank line before the movements"))?;
     let map = {
-        let mut robot: Pos = (-1, -1);
-        let mut tiles: HashMap<Pos, char> = HashMap::new();
-        let mut w = 0;
-        let mut h = 0;
-        sections[0].trim().lines().enumerate().for_each(|(y, s)| {
-            let y = y as i32;
-            h = h.max(y + 1);
-            s.chars().enumerate().for_each(|(x, ch)| {
-                let x = x as i32;
-                w = w.max(x + 1);
-                match ch {
-                    '@' => {
-                        robot = (x, y);
-                    }
-                    'O' | '#' => {
-                        tiles.insert((x, y), ch);
-                    }
-                    _ => {}
-                };
-            });
-        });
-        Map { robot, tiles, w, h }
+        let mut tiles = Grid::parse(&src, section, |c| match c {
+            '#' | 'O' | '.' | '@' => Some(c),
+            _ => None,
+        })?;
+        let robot = tiles.find(&'@').ok_or_else(|| {
+            src.error(section, "no robot '@' in the warehouse")
+        })?;
+        tiles[robot] = '.';
+        Map { robot, tiles }
     };
-    let movements = sections[1]
+    let movements = moves
         .trim()
         .lines()
-        .flat_map(|s| {
-            s.trim().chars().map(|ch| match ch {
-                '^' => 0,
-                '>' => 1,
-                'v' => 2,
-                '<' => 3,
-                _ => panic!(),
+        .map(|s| s.trim())
+        .flat_map(|s| s.char_indices().map(move |(i, ch)| (&s[i..], ch)))
+        .map(|(at, ch)| {
+            Dir::from_char(ch).ok_or_else(|| {
+                src.error(at, format!("unexpected movement {:?}", ch))
             })
         })
-        .collect();
-    (map, movements)
+        .collect::<Result<_, _>>()?;
+    Ok((map, movements))
 }
 
 fn enlarge(map: Map) -> LargeMap {
+    let mut tiles = Grid::new(map.tiles.width() * 2, map.tiles.height(), '.');
+    for (p, ch) in map.tiles.iter() {
+        let pair = match ch {
+            'O' => ['[', ']'],
+            _ => [*ch; 2],
+        };
+        let left = Point::new(p.x * 2, p.y);
+        tiles[left] = pair[0];
+        tiles[left.step(Dir::Right)] = pair[1];
+    }
     LargeMap {
-        robot: (map.robot.0 * 2, map.robot.1),
-        tiles: map
-            .tiles
-            .iter()
-            .flat_map(|(&(x, y), ch)| match ch {
-                'O' => vec![((x * 2, y), '['), ((x * 2 + 1, y), ']')],
-                '#' => vec![((x * 2, y), '#'), ((x * 2 + 1, y), '#')],
-                _ => vec![],
-            })
-            .collect(),
-        w: map.w * 2,
-        h: map.h,
+        robot: Point::new(map.robot.x * 2, map.robot.y),
+        tiles,
     }
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let (mut map, movements) = parse_input(input);
+pub fn part_one((map, movements): &Input) -> i32 {
+    let mut map = map.clone();
     movements.iter().for_each(|dir| map.move_robot(*dir));
-    map.tiles
-        .into_iter()
-        .filter(|(_, ch)| ch == &'O')
-        .map(|((x, y), _)| x + 100 * y)
-        .sum()
+    map.tiles.find_all(&'O').map(|p| p.x + 100 * p.y).sum()
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let (map, movements) = parse_input(input);
-    let mut map = enlarge(map);
+pub fn part_two((map, movements): &Input) -> i32 {
+    let mut map = enlarge(map.clone());
     movements.iter().for_each(|dir| map.move_robot(*dir));
-    map.tiles
+    map.tiles.find_all(&'[').map(|p| p.x + 100 * p.y).sum()
+}
+
+/// The robot pushing boxes around the wide warehouse of part two, one
+/// movement at a time.
+pub fn frames((map, movements): &Input) -> impl Iterator<Item = Frame> + '_ {
+    let mut map = enlarge(map.clone());
+    let moves = movements.len();
+    let steps = movements.iter().map(Some);
+    [None]
         .into_iter()
-        .filter(|(_, ch)| ch == &'[')
-        .map(|((x, y), _)| x + 100 * y)
-        .sum()
+        .chain(steps)
+        .enumerate()
+        .map(move |(i, dir)| {
+            let caption = match dir {
+                Some(dir) => {
+                    map.move_robot(*dir);
+                    format!("move {}/{}: {}", i, moves, dir)
+                }
+                None => format!("move 0/{}", moves),
+            };
+            Frame::new(caption, &map.draw(), |c| match c {
+                '#' => Color::Gray,
+                '[' | ']' => Color::Yellow,
+                '@' => Color::Red,
+                _ => Color::Default,
+            })
+        })
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+
+    fn frames<'a>(
+        input: &'a Input,
+        _: &'a (),
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        Box::new(frames(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::read_named_example;
 
     #[test]
     fn example_smaller() {
-        const INPUT: &str = concat!(
-            "########\n",
-            "#..O.O.#\n",
-            "##@.O..#\n",
-            "#...O..#\n",
-            "#.#.O..#\n",
-            "#...O..#\n",
-            "#......#\n",
-            "########\n",
-            "\n",
-            "<^^>>>vv<v>>v<<"
-        );
+        let input = read_named_example(15, "small");
         // for this smaller example,
         // the sum of all boxes' GPS coordinates is 2028
-        let (mut map, movements) = parse_input(INPUT);
+        let (mut map, movements) = parse_input(&input).unwrap();
         movements.iter().for_each(|dir| map.move_robot(*dir));
-        let sum: i32 = map
-            .tiles
-            .into_iter()
-            .filter(|(_, ch)| ch == &'O')
-            .map(|((x, y), _)| x + 100 * y)
-            .sum();
+        let sum: i32 =
+            map.tiles.find_all(&'O').map(|p| p.x + 100 * p.y).sum();
         assert_eq!(sum, 2028);
     }
 
     #[test]
     fn example_smaller_2() {
-        const INPUT: &str = concat!(
-            "#######\n",
-            "#...#.#\n",
-            "#.....#\n",
-            "#..OO@#\n",
-            "#..O..#\n",
-            "#.....#\n",
-            "#######\n",
-            "\n",
-            "<vv<<^^<<^^"
-        );
-        let (map, movements) = parse_input(INPUT);
+        let input = read_named_example(15, "wide");
+        let (map, movements) = parse_input(&input).unwrap();
         let mut map = enlarge(map);
         movements.iter().for_each(|dir| map.move_robot(*dir));
-        assert_eq!(map.tiles.get(&(5, 1)), Some(&'['));
+        assert_eq!(map.tiles.get(Point::new(5, 1)), Some(&'['));
+    }
+
+    #[test]
+    fn invalid_movement() {
+        let err = parse_input("#@.#\n\n<>x").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (15, 3, 3));
     }
 
     #[test]
     fn example() {
-        let input = read_example(15);
+        let input = parse_input(&read_example(15)).unwrap();
         assert_eq!(part_one(&input), 10092);
         assert_eq!(part_two(&input), 9021);
     }
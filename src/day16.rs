@@ -1,122 +1,159 @@
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
 use std::collections::HashSet;
 
-const DIRS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-type Pos = (i32, i32);
+use crate::search;
+use crate::Answer;
+use crate::Color;
+use crate::Dir;
+use crate::Frame;
+use crate::Grid;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.trim().lines().map(|s| s.chars().collect()).collect()
+/// The maze with its start and end tiles.
+type Input = (Grid<char>, Point, Point);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day16::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty maze"));
+    }
+    let maze = Grid::parse(&src, input, Some)?;
+    let start = maze
+        .find(&'S')
+        .ok_or_else(|| src.eof("no start 'S' in the maze"))?;
+    let end = maze
+        .find(&'E')
+        .ok_or_else(|| src.eof("no end 'E' in the maze"))?;
+    Ok((maze, start, end))
 }
 
-fn find_ch(maze: &[Vec<char>], c: char) -> Option<Pos> {
-    maze.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, &ch)| ch == c)
-                .map(|(x, _)| (x as i32, y as i32))
-                .collect::<Vec<_>>()
-        })
-        .next()
+/// The lowest score from the start to the end and, if `best_path` is
+/// set, the tiles on a path with that score. `None` if the end cannot be
+/// reached.
+fn find_min_score(
+    (maze, start, end): &Input,
+    best_path: bool,
+) -> Option<(i32, HashSet<Point>)> {
+    let open = |p: Point| !matches!(maze.get(p), None | Some('#'));
+    let paths = search::dijkstra(
+        // The reindeer starts facing east.
+        [(*start, Dir::Right)],
+        |&(p, d)| {
+            let step = (p.step(d), d);
+            [(p, d.turn_right()), (p, d.turn_left())]
+                .map(|turn| (turn, 1000))
+                .into_iter()
+                .chain(open(step.0).then_some((step, 1)))
+        },
+        |&(p, _)| p == *end,
+    );
+    let score = paths.goal_distance()?;
+    if !best_path {
+        return Some((score, HashSet::new()));
+    }
+    let tiles: HashSet<Point> = paths
+        .on_paths(paths.goals())
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect();
+    Some((score, tiles))
 }
 
-fn find_min_score(maze: &[Vec<char>], best_path: bool) -> (i32, usize) {
-    let h = maze.len() as i32;
-    let w = maze[0].len() as i32;
-    let mut queue: BinaryHeap<(i32, Pos, usize, Vec<Pos>)> =
-        BinaryHeap::new();
-    let start = find_ch(maze, 'S').unwrap();
-    let end = find_ch(maze, 'E').unwrap();
-    queue.push((0, start, 0, vec![start]));
-    let mut visited: HashMap<(Pos, usize), i32> = HashMap::new();
-    let mut answer = i32::MIN;
-    let mut tails: HashSet<Pos> = HashSet::new();
-    while let Some((score, p, d, t)) = queue.pop() {
-        if p.0 < 0 || p.0 >= w || p.1 < 0 || p.1 > h {
-            continue;
-        }
-        if maze[p.1 as usize][p.0 as usize] == '#' {
-            continue;
-        }
-        if score < answer {
-            break;
-        }
-        if p == end {
-            answer = score;
-            if !best_path {
-                break;
-            }
-            tails.extend(t.iter().cloned());
-            continue;
-        }
-        if let Some(visited_score) = visited.get(&(p, d)) {
-            if !best_path || score < *visited_score {
-                continue;
+pub fn part_one(maze: &Input) -> Option<i32> {
+    find_min_score(maze, false).map(|(score, _)| score)
+}
+
+pub fn part_two(maze: &Input) -> Option<usize> {
+    find_min_score(maze, true).map(|(_, tiles)| tiles.len())
+}
+
+/// The maze with every tile on a best path marked `O`.
+pub fn frames(input: &Input) -> impl Iterator<Item = Frame> {
+    let best = find_min_score(input, true);
+    let mut drawn = input.0.clone();
+    let caption = match &best {
+        Some((score, tiles)) => {
+            for &p in tiles {
+                if drawn[p] == '.' {
+                    drawn[p] = 'O';
+                }
             }
+            format!("score {}, {} best-path tiles", score, tiles.len())
         }
-        visited.insert((p, d), score);
-        let next = (p.0 + DIRS[d].0, p.1 + DIRS[d].1);
-        let mut next_tiles = t.clone();
-        if best_path {
-            next_tiles.push(next);
-        }
-        queue.push((score - 1, next, d, next_tiles));
-        queue.push((score - 1000, p, (d + 1) % 4, t.clone()));
-        queue.push((score - 1000, p, (d + 3) % 4, t.clone()));
-    }
-    (-answer, tails.len())
+        None => "no path from S to E".to_string(),
+    };
+    let frame = Frame::new(caption, &drawn, |c| match c {
+        '#' => Color::Gray,
+        'O' => Color::Green,
+        'S' | 'E' => Color::Red,
+        _ => Color::Default,
+    });
+    [frame].into_iter()
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let maze = parse_input(input);
-    let (score, _) = find_min_score(&maze, false);
-    score
-}
+pub struct Day16;
 
-pub fn part_two(input: &str) -> usize {
-    let maze = parse_input(input);
-    let (_, tiles) = find_min_score(&maze, true);
-    tiles
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+
+    fn frames<'a>(
+        input: &'a Input,
+        _: &'a (),
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        Box::new(frames(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::read_named_example;
 
     #[test]
     fn example_2() {
-        const INPUT: &str = concat!(
-            "#################\n",
-            "#...#...#...#..E#\n",
-            "#.#.#.#.#.#.#.#.#\n",
-            "#.#.#.#...#...#.#\n",
-            "#.#.#.#.###.#.#.#\n",
-            "#...#.#.#.....#.#\n",
-            "#.#.#.#.#.#####.#\n",
-            "#.#...#.#.#.....#\n",
-            "#.#.#####.#.###.#\n",
-            "#.#.#.......#...#\n",
-            "#.#.###.#####.###\n",
-            "#.#.#...#.....#.#\n",
-            "#.#.#.#####.###.#\n",
-            "#.#.#.........#.#\n",
-            "#.#.#.#########.#\n",
-            "#S#.............#\n",
-            "#################\n",
-        );
-        let maze = parse_input(INPUT);
-        let (score, tiles) = find_min_score(&maze, true);
+        let maze = parse_input(&read_named_example(16, "second")).unwrap();
+        let (score, tiles) = find_min_score(&maze, true).unwrap();
         assert_eq!(score, 11048);
-        assert_eq!(tiles, 64);
+        assert_eq!(tiles.len(), 64);
+    }
+
+    #[test]
+    fn missing_end() {
+        let err = parse_input("###\n#S#\n###\n").unwrap_err();
+        assert_eq!(err.message, "no end 'E' in the maze");
+        assert_eq!((err.line, err.column), (4, 1));
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let maze = parse_input("#####\n#S#E#\n#####\n").unwrap();
+        assert_eq!(part_one(&maze), None);
+        assert_eq!(part_two(&maze), None);
     }
 
     #[test]
     fn example() {
-        let input = read_example(16);
-        assert_eq!(part_one(&input), 7036);
-        assert_eq!(part_two(&input), 45);
+        let input = parse_input(&read_example(16)).unwrap();
+        assert_eq!(part_one(&input), Some(7036));
+        assert_eq!(part_two(&input), Some(45));
     }
 }
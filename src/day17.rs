@@ -1,5 +1,10 @@
-#[derive(Clone)]
-struct Computer {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+#[derive(Debug, Clone)]
+pub struct Computer {
     a: u64,
     b: u64,
     c: u64,
@@ -32,7 +37,8 @@ impl Computer {
             /* cdv */ 7 => self.c = self.a >> self.combo(operand),
             _ => {}
         }
-        self.p < program.len()
+        // A jump can land on the last value, which has no operand.
+        self.p + 1 < program.len()
     }
 
     fn execute(&mut self, program: &[u8]) -> Vec<u8> {
@@ -43,33 +49,62 @@ impl Computer {
     }
 }
 
-fn parse_input(input: &str) -> (Computer, Vec<u8>) {
-    const REGI_PREFIX_LEN: usize = "Register #:".len();
-    const PROG_PREFIX_LEN: usize = "Program:".len();
-    let lines: Vec<&str> = input.trim().lines().collect();
-    let a = lines[0][REGI_PREFIX_LEN..].trim().parse().unwrap();
-    let b = lines[1][REGI_PREFIX_LEN..].trim().parse().unwrap();
-    let c = lines[2][REGI_PREFIX_LEN..].trim().parse().unwrap();
-    let program = lines[4][PROG_PREFIX_LEN..]
+type Input = (Computer, Vec<u8>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day17::DAY, input);
+    let lines: Vec<&str> = input
         .trim()
-        .split(',')
-        .map(|s| s.trim().parse().unwrap())
+        .lines()
+        .filter(|s| !s.trim().is_empty())
         .collect();
+    let field = |i: usize, prefix: &str| match lines.get(i) {
+        Some(s) => s
+            .strip_prefix(prefix)
+            .map(|s| s.trim())
+            .ok_or_else(|| src.error(s, format!("expected {:?}", prefix))),
+        None => Err(src.eof(format!("missing {:?}", prefix))),
+    };
+    let a = src.number(field(0, "Register A:")?)?;
+    let b = src.number(field(1, "Register B:")?)?;
+    let c = src.number(field(2, "Register C:")?)?;
+    let values: Vec<&str> =
+        field(3, "Program:")?.split(',').map(|s| s.trim()).collect();
+    let mut program = Vec::new();
+    for (i, &s) in values.iter().enumerate() {
+        let value: u8 = src.number(s)?;
+        if value > 7 {
+            return Err(
+                src.error(s, format!("{} is not a 3-bit value", value))
+            );
+        }
+        // Operand 7 is reserved for combo operands.
+        let combo = i % 2 == 1 && matches!(program[i - 1], 0 | 2 | 5 | 6 | 7);
+        if combo && value == 7 {
+            return Err(src.error(s, "combo operand 7 is reserved"));
+        }
+        program.push(value);
+    }
+    if program.len() % 2 == 1 {
+        let last = values[values.len() - 1];
+        return Err(src.error(last, "instruction without an operand"));
+    }
 
-    (Computer { a, b, c, p: 0 }, program)
+    Ok((Computer { a, b, c, p: 0 }, program))
 }
 
-pub fn part_one(input: &str) -> String {
-    let (mut comp, program) = parse_input(input);
-    comp.execute(&program)
+pub fn part_one((comp, program): &Input) -> String {
+    comp.clone()
+        .execute(program)
         .iter()
         .map(|v| v.to_string())
         .collect::<Vec<String>>()
         .join(",")
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let (comp, program) = parse_input(input);
+/// The lowest value for register A that makes the program output itself,
+/// if there is one.
+pub fn part_two((comp, program): &Input) -> Option<u64> {
     let mut candidates = vec![0];
     for i in (0..program.len()).rev() {
         let target = &program[i..];
@@ -82,32 +117,72 @@ pub fn part_two(input: &str) -> u64 {
                     .filter(|&a| {
                         let mut comp = comp.clone();
                         comp.a = a;
-                        comp.execute(&program) == target
+                        comp.execute(program) == target
                     })
                     .collect::<Vec<_>>()
             })
             .collect();
     }
-    candidates.into_iter().min().unwrap()
+    candidates.into_iter().min()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::read_named_example;
 
-    const INPUT_P2: &str = concat!(
-        "Register A: 2024\n",
-        "Register B: 0\n",
-        "Register C: 0\n",
-        "\n",
-        "Program: 0,3,5,4,3,0"
-    );
+    #[test]
+    fn truncated_input() {
+        let err = parse_input("Register A: 1\nRegister B: 0\n").unwrap_err();
+        assert_eq!(err.message, "missing \"Register C:\"");
+    }
+
+    #[test]
+    fn invalid_program() {
+        let registers = "Register A: 1\nRegister B: 0\nRegister C: 0\n\n";
+        let error = |program: &str| {
+            let err = parse_input(&format!("{}{}", registers, program));
+            let err = err.unwrap_err();
+            (err.line, err.column, err.message)
+        };
+        let odd = error("Program: 0,1,0");
+        assert_eq!(odd, (5, 14, "instruction without an operand".into()));
+        let large = error("Program: 0,1,8,1");
+        assert_eq!(large, (5, 14, "8 is not a 3-bit value".into()));
+        let combo = error("Program: 1,7,5,7");
+        assert_eq!(combo, (5, 16, "combo operand 7 is reserved".into()));
+    }
 
     #[test]
     fn example() {
-        let input = read_example(17);
+        let input = parse_input(&read_example(17)).unwrap();
         assert_eq!(part_one(&input), "4,6,3,5,6,3,5,2,1,0");
-        assert_eq!(part_two(INPUT_P2), 117440);
+        let input = parse_input(&read_named_example(17, "quine")).unwrap();
+        assert_eq!(part_two(&input), Some(117440));
+        // The first example is not a quine.
+        assert_eq!(part_two(&parse_input(&read_example(17)).unwrap()), None);
     }
 }
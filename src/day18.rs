@@ -1,60 +1,126 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use std::collections::HashSet;
+use crate::params;
+use crate::search;
+use crate::Answer;
+use crate::Grid;
+use crate::Image;
+use crate::ParseError;
+use crate::Paths;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-type Pos = (i8, i8);
-const DIRS: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+type Input = Vec<Point>;
 
-fn parse_input(input: &str) -> Vec<(i8, i8)> {
+/// The largest `target` coordinate accepted, which keeps the memory grid
+/// to a million tiles.
+const MAX_TARGET: i32 = 999;
+
+/// The bottom-right corner of the memory space and how many bytes have
+/// fallen for part one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub target: Point,
+    pub bytes: usize,
+}
+
+impl crate::Params for Params {
+    fn example() -> Self {
+        Params {
+            target: Point::new(6, 6),
+            bytes: 12,
+        }
+    }
+
+    fn input() -> Self {
+        Params {
+            target: Point::new(70, 70),
+            bytes: 1024,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "target" => {
+                self.target = Point::from(params::pair(key, value, ',')?);
+                let range = 0..=MAX_TARGET;
+                if !range.contains(&self.target.x)
+                    || !range.contains(&self.target.y)
+                {
+                    let expected =
+                        format!("coordinates from 0 to {}", MAX_TARGET);
+                    return Err(params::invalid(key, value, &expected));
+                }
+            }
+            "bytes" => self.bytes = params::value(key, value)?,
+            _ => return Err(params::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day18::DAY, input);
     input
         .trim()
         .lines()
-        .map(|s| {
-            s.splitn(2, ',')
-                .map(|v| v.parse::<i8>().unwrap())
-                .collect::<Vec<_>>()
+        .map(|s| match s.split_once(',') {
+            Some((x, y)) => Ok(Point::new(src.number(x)?, src.number(y)?)),
+            None => Err(src.error(s, "expected a coordinate like 5,4")),
         })
-        .map(|v| (v[0], v[1]))
         .collect()
 }
 
-fn shortest_steps(positions: &[Pos], start: Pos, end: Pos) -> Option<usize> {
-    let corrupted: HashSet<Pos> = positions.iter().cloned().collect();
-    let mut visited = HashSet::new();
-    let mut queue = BinaryHeap::new();
-    queue.push(Reverse((0, start)));
-    while let Some(Reverse((steps, node))) = queue.pop() {
-        if node == end {
-            return Some(steps);
-        }
-        if visited.contains(&node) {
-            continue;
-        }
-        visited.insert(node);
-        for (dx, dy) in DIRS {
-            let next = (node.0 + dx, node.1 + dy);
-            if next.0 < 0 || next.0 > end.0 || next.1 < 0 || next.1 > end.1 {
-                continue;
-            }
-            if corrupted.contains(&next) {
-                continue;
-            }
-            queue.push(Reverse((steps + 1, next)));
+/// The memory space up to `end` with the fallen bytes marked.
+fn corrupted(positions: &[Point], end: Point) -> Grid<bool> {
+    let (w, h) = (end.x as usize + 1, end.y as usize + 1);
+    let mut corrupted = Grid::new(w, h, false);
+    for &p in positions {
+        if let Some(c) = corrupted.get_mut(p) {
+            *c = true;
         }
     }
-    None
+    corrupted
+}
+
+fn escape(
+    corrupted: &Grid<bool>,
+    start: Point,
+    end: Point,
+) -> Paths<Point, usize> {
+    let neighbours = |&p: &Point| {
+        corrupted
+            .neighbours(p)
+            .filter(|&q| !corrupted[q])
+            .map(|q| (q, 1))
+    };
+    let heuristic = |p: &Point| p.manhattan(end) as usize;
+    search::astar([start], neighbours, heuristic, |&p| p == end)
+}
+
+pub fn shortest_steps(
+    positions: &[Point],
+    start: Point,
+    end: Point,
+) -> Option<usize> {
+    escape(&corrupted(positions, end), start, end).goal_distance()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let positions = parse_input(input);
-    shortest_steps(&positions[..1024], (0, 0), (70, 70)).unwrap()
+/// The fewest steps to the exit, or `None` if the bytes have already cut
+/// it off.
+pub fn part_one(positions: &Input, params: &Params) -> Option<usize> {
+    let bytes = params.bytes.min(positions.len());
+    shortest_steps(&positions[..bytes], Point::ZERO, params.target)
 }
 
-fn privent_coordinate(positions: &[Pos], start: Pos, end: Pos) -> String {
+pub fn privent_coordinate(
+    positions: &[Point],
+    start: Point,
+    end: Point,
+) -> String {
     let mut a = 0;
     let mut b = positions.len();
     loop {
-        let m = (a + b + 1) / 2;
+        let m = (a + b).div_ceil(2);
         if b == m {
             break;
         }
@@ -64,29 +130,97 @@ fn privent_coordinate(positions: &[Pos], start: Pos, end: Pos) -> String {
         }
     }
     if b < positions.len() {
-        format!("{},{}", positions[a].0, positions[a].1)
+        positions[a].to_string()
     } else {
         "None".to_string()
     }
 }
 
-pub fn part_two(input: &str) -> String {
-    let positions = parse_input(input);
-    privent_coordinate(&positions, (0, 0), (70, 70))
+pub fn part_two(positions: &Input, params: &Params) -> String {
+    privent_coordinate(positions, Point::ZERO, params.target)
+}
+
+/// The memory space after the bytes of part one have fallen, with the
+/// shortest path out in yellow.
+pub fn image(positions: &Input, params: &Params) -> Image {
+    let bytes = params.bytes.min(positions.len());
+    let corrupted = corrupted(&positions[..bytes], params.target);
+    let path = escape(&corrupted, Point::ZERO, params.target)
+        .path(&params.target)
+        .unwrap_or_default();
+    let mut tiles = corrupted.map(|&c| if c { '#' } else { '.' });
+    for p in path {
+        tiles[p] = 'O';
+    }
+    Image::rgb(&tiles, |&c| match c {
+        '#' => [150, 30, 30],
+        'O' => [250, 210, 50],
+        _ => [20, 20, 20],
+    })
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "RAM Run";
+
+    type Input = Input;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, params: &Params) -> Answer {
+        part_one(input, params).into()
+    }
+
+    fn part_two(input: &Input, params: &Params) -> Answer {
+        part_two(input, params).into()
+    }
+
+    fn image(input: &Input, params: &Params) -> Option<Image> {
+        Some(image(input, params))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::Params as _;
 
     #[test]
     fn example() {
         let input = read_example(18);
-        let positions = parse_input(&input);
-        let steps = shortest_steps(&positions[..12], (0, 0), (6, 6)).unwrap();
+        let positions = parse_input(&input).unwrap();
+        let steps =
+            shortest_steps(&positions[..12], Point::ZERO, Point::new(6, 6))
+                .unwrap();
         assert_eq!(steps, 22);
-        let p = privent_coordinate(&positions, (0, 0), (6, 6));
+        let p = privent_coordinate(&positions, Point::ZERO, Point::new(6, 6));
         assert_eq!(p, "6,1");
+        assert_eq!(part_one(&positions, &Params::example()), Some(22));
+        assert_eq!(part_two(&positions, &Params::example()), "6,1");
+    }
+
+    #[test]
+    fn params() {
+        let overrides = [("target".to_string(), "-1,5".to_string())];
+        assert_eq!(
+            Params::resolve("input", &overrides).unwrap_err(),
+            "invalid value \"-1,5\" for target, expected coordinates from 0 to 999"
+        );
+        let overrides = [("target".to_string(), "5,1000".to_string())];
+        assert!(Params::resolve("input", &overrides).is_err());
+    }
+
+    #[test]
+    fn unreachable() {
+        let input = parse_input(&read_example(18)).unwrap();
+        let mut params = Params::example();
+        params.bytes = input.len();
+        assert_eq!(part_one(&input, &params), None);
     }
 }
@@ -1,29 +1,37 @@
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> (Vec<&str>, Vec<&str>) {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = (Vec<String>, Vec<String>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day19::DAY, input);
     let mut lines = input.trim().lines();
     let patterns = lines
         .next()
-        .unwrap()
+        .ok_or_else(|| src.eof("missing towel patterns"))?
         .trim()
         .split(',')
-        .map(|s| s.trim())
+        .map(|s| s.trim().to_string())
         .collect();
-    let designs = lines.skip(1).collect();
-    (patterns, designs)
+    let designs = lines.skip(1).map(|s| s.to_string()).collect();
+    Ok((patterns, designs))
 }
 
-fn is_possible(design: &str, patterns: &[&str]) -> bool {
+fn is_possible(design: &str, patterns: &[String]) -> bool {
     design.is_empty()
         || patterns.iter().any(|pattern| {
-            design.starts_with(pattern)
+            design.starts_with(pattern.as_str())
                 && is_possible(&design[pattern.len()..], patterns)
         })
 }
 
 fn all_possible<'a>(
     design: &'a str,
-    patterns: &[&str],
+    patterns: &[String],
     cache: &mut HashMap<&'a str, usize>,
 ) -> usize {
     if design.is_empty() {
@@ -34,7 +42,7 @@ fn all_possible<'a>(
     } else {
         let count = patterns
             .iter()
-            .filter(|&&pattern| design.starts_with(pattern))
+            .filter(|pattern| design.starts_with(pattern.as_str()))
             .map(|pattern| {
                 all_possible(&design[pattern.len()..], patterns, cache)
             })
@@ -45,7 +53,7 @@ fn all_possible<'a>(
 }
 
 #[allow(dead_code)]
-fn is_possible_dp(design: &str, patterns: &[&str]) -> bool {
+fn is_possible_dp(design: &str, patterns: &[String]) -> bool {
     let n = design.len();
     let mut dp = vec![false; n + 1];
     dp[0] = true;
@@ -54,14 +62,14 @@ fn is_possible_dp(design: &str, patterns: &[&str]) -> bool {
             .iter()
             .filter(|pattern| i >= pattern.len())
             .map(|pattern| (i - pattern.len(), pattern))
-            .filter(|(j, pattern)| design[*j..].starts_with(*pattern))
+            .filter(|(j, pattern)| design[*j..].starts_with(pattern.as_str()))
             .for_each(|(j, _)| dp[i] |= dp[j]);
     }
     dp[n]
 }
 
 #[allow(dead_code)]
-fn all_possible_dp(design: &str, patterns: &[&str]) -> usize {
+fn all_possible_dp(design: &str, patterns: &[String]) -> usize {
     let n = design.len();
     let mut dp = vec![0; n + 1];
     dp[0] = 1;
@@ -70,29 +78,49 @@ fn all_possible_dp(design: &str, patterns: &[&str]) -> usize {
             .iter()
             .filter(|pattern| i >= pattern.len())
             .map(|pattern| (i - pattern.len(), pattern))
-            .filter(|(j, pattern)| design[*j..].starts_with(*pattern))
+            .filter(|(j, pattern)| design[*j..].starts_with(pattern.as_str()))
             .for_each(|(j, _)| dp[i] += dp[j]);
     }
     dp[n]
 }
 
-pub fn part_one(input: &str) -> usize {
-    let (patterns, designs) = parse_input(input);
+pub fn part_one((patterns, designs): &Input) -> usize {
     designs
         .iter()
-        .filter(|design| is_possible(design, &patterns))
+        .filter(|design| is_possible(design, patterns))
         .count()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let (patterns, designs) = parse_input(input);
+pub fn part_two((patterns, designs): &Input) -> usize {
     let mut cache: HashMap<&str, usize> = HashMap::new();
     designs
         .iter()
-        .map(|design| all_possible(design, &patterns, &mut cache))
+        .map(|design| all_possible(design, patterns, &mut cache))
         .sum()
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Linen Layout";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,7 +129,7 @@ mod tests {
     #[test]
     fn example_dp() {
         let input = read_example(19);
-        let (patterns, designs) = parse_input(&input);
+        let (patterns, designs) = parse_input(&input).unwrap();
         let p1: usize = designs
             .iter()
             .filter(|design| is_possible_dp(design, &patterns))
@@ -116,7 +144,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(19);
+        let input = parse_input(&read_example(19)).unwrap();
         assert_eq!(part_one(&input), 6);
         assert_eq!(part_two(&input), 16);
     }
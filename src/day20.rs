@@ -1,74 +1,62 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
 use std::collections::HashMap;
 
-type Pos = (i32, i32);
-type Grid = HashMap<Pos, char>;
-type Distances = HashMap<Pos, i32>;
-type Counts = HashMap<i32, usize>;
-
-const DIRS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-
-fn parse_input(input: &str) -> Grid {
-    input
-        .trim()
-        .lines()
-        .enumerate()
-        .flat_map(|(y, s)| {
-            s.chars()
-                .enumerate()
-                .map(|(x, c)| ((x as i32, y as i32), c))
-                .collect::<HashMap<_, _>>()
-        })
-        .collect()
-}
+use crate::params;
+use crate::search;
+use crate::Answer;
+use crate::Grid;
+use crate::Image;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
 
-fn find_char(grid: &Grid, ch: char) -> Option<Pos> {
-    grid.iter().find(|(_, &c)| c == ch).map(|(&p, _)| p)
-}
+pub type Distances = HashMap<Point, i32>;
+pub type Counts = HashMap<i32, usize>;
 
-fn build_dists(grid: &Grid, position: Pos) -> Distances {
-    let mut dists = Distances::new();
-    let mut queue: BinaryHeap<Reverse<(i32, Pos)>> = BinaryHeap::new();
-    queue.push(Reverse((0, position)));
-    while let Some(Reverse((d, p))) = queue.pop() {
-        match grid.get(&p) {
-            Some('#') => continue,
-            Some(_) => {}
-            None => continue,
-        }
-        if dists.contains_key(&p) {
-            continue;
-        }
-        dists.insert(p, d);
-        for (dx, dy) in DIRS {
-            let next = (p.0 + dx, p.1 + dy);
-            if grid.contains_key(&next) {
-                queue.push(Reverse((d + 1, next)));
-            }
-        }
+/// The racetrack with its start tile.
+type Input = (Grid<char>, Point);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day20::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("empty racetrack"));
+    }
+    let grid = Grid::parse(&src, input, Some)?;
+    let start = grid
+        .find(&'S')
+        .ok_or_else(|| src.eof("no start 'S' on the racetrack"))?;
+    if grid.find(&'E').is_none() {
+        return Err(src.eof("no end 'E' on the racetrack"));
     }
-    dists
+    Ok((grid, start))
 }
 
-fn cheats_count(dists: &Distances, mcd: i32, msd: i32) -> Counts {
+pub fn build_dists(grid: &Grid<char>, position: Point) -> Distances {
+    let track = |&p: &Point| grid.neighbours(p).filter(|&q| grid[q] != '#');
+    search::bfs([position], track, |_| false)
+        .distances()
+        .map(|(&p, d)| (p, d as i32))
+        .collect()
+}
+
+pub fn cheats_count(dists: &Distances, mcd: i32, msd: i32) -> Counts {
     let mut counts = Counts::new();
 
     let delta = {
-        let mut v: Vec<Pos> = Vec::new();
+        let mut v: Vec<(i32, i32)> = Vec::new();
         for i in 1..=mcd {
             v.extend([(i, 0), (-i, 0), (0, i), (0, -i)]);
             v.extend((1..i).map(|dx| (dx, i - dx)).flat_map(|(dx, dy)| {
                 vec![(dx, dy), (dx, -dy), (-dx, dy), (-dx, -dy)]
             }));
         }
-        v
+        v.into_iter().map(Point::from).collect::<Vec<_>>()
     };
 
     dists.iter().for_each(|(p1, d1)| {
         delta
             .iter()
-            .map(|(dx, dy)| ((p1.0 + dx, p1.1 + dy), dx.abs() + dy.abs()))
+            .map(|&d| (*p1 + d, d.manhattan(Point::ZERO)))
             .filter(|(p2, _)| dists.contains_key(p2))
             .map(|(p2, cd)| (p2, dists[&p2], cd))
             .map(|(_, d2, cd)| d2 - d1 - cd)
@@ -81,12 +69,39 @@ fn cheats_count(dists: &Distances, mcd: i32, msd: i32) -> Counts {
     counts
 }
 
-pub fn part_one(input: &str) -> usize {
-    let grid = parse_input(input);
-    let start = find_char(&grid, 'S').unwrap();
-    let dists = build_dists(&grid, start);
+/// The least number of picoseconds a cheat must save to be counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub saves: i32,
+}
+
+impl crate::Params for Params {
+    fn example() -> Self {
+        Params { saves: 50 }
+    }
+
+    fn input() -> Self {
+        Params { saves: 100 }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "saves" => {
+                self.saves = params::value(key, value)?;
+                if self.saves < 0 {
+                    return Err(params::invalid(key, value, "a count"));
+                }
+            }
+            _ => return Err(params::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub fn part_one((grid, start): &Input, params: &Params) -> usize {
+    let dists = build_dists(grid, *start);
     let mcd = 2;
-    let msd = 100;
+    let msd = params.saves;
     let counts = cheats_count(&dists, mcd, msd);
     counts
         .iter()
@@ -95,12 +110,10 @@ pub fn part_one(input: &str) -> usize {
         .sum()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let grid = parse_input(input);
-    let start = find_char(&grid, 'S').unwrap();
-    let dists = build_dists(&grid, start);
+pub fn part_two((grid, start): &Input, params: &Params) -> usize {
+    let dists = build_dists(grid, *start);
     let mcd = 20;
-    let msd = 100;
+    let msd = params.saves;
     let counts = cheats_count(&dists, mcd, msd);
     counts
         .iter()
@@ -109,17 +122,56 @@ pub fn part_two(input: &str) -> usize {
         .sum()
 }
 
+/// The racetrack in gray, brighter the farther along from the start, with
+/// the walls black.
+pub fn image((grid, start): &Input) -> Image {
+    let dists = build_dists(grid, *start);
+    let far = dists.values().copied().max().unwrap_or(0).max(1);
+    let mut field = grid.map(|_| 0u8);
+    for (&p, &d) in &dists {
+        field[p] = 55 + (200 * d / far) as u8;
+    }
+    Image::Gray(field)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Race Condition";
+
+    type Input = Input;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, params: &Params) -> Answer {
+        part_one(input, params).into()
+    }
+
+    fn part_two(input: &Input, params: &Params) -> Answer {
+        part_two(input, params).into()
+    }
+
+    fn image(input: &Input, _: &Params) -> Option<Image> {
+        Some(image(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read_example;
+    use crate::Params as _;
 
     #[test]
     fn example() {
         let input = read_example(20);
-        let grid = parse_input(&input);
-        let start = find_char(&grid, 'S').unwrap();
-        let dists = build_dists(&grid, start);
+        let input = parse_input(&input).unwrap();
+        let (grid, start) = &input;
+        let dists = build_dists(grid, *start);
         // part_one
         let counts = cheats_count(&dists, 2, 0);
         assert_eq!(counts[&2], 14);
@@ -148,5 +200,15 @@ mod tests {
         assert_eq!(counts[&72], 22);
         assert_eq!(counts[&74], 4);
         assert_eq!(counts[&76], 3);
+        assert_eq!(part_two(&input, &Params::example()), 285);
+    }
+
+    #[test]
+    fn params() {
+        let overrides = [("saves".to_string(), "-1".to_string())];
+        assert_eq!(
+            Params::resolve("input", &overrides).unwrap_err(),
+            "invalid value \"-1\" for saves, expected a count"
+        );
     }
 }
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
-use std::collections::VecDeque;
 use std::sync::OnceLock;
 
-type Keypad = HashMap<(i8, i8), u8>;
+use crate::search;
+use crate::Answer;
+use crate::Dir;
+use crate::ParseError;
+use crate::Point;
+use crate::Solution;
+use crate::Source;
+
+type Keypad = HashMap<Point, u8>;
 static NUM_KEYPAD: OnceLock<Keypad> = OnceLock::new();
 static DIR_KEYPAD: OnceLock<Keypad> = OnceLock::new();
 
@@ -21,7 +28,7 @@ fn init_keypad(keypad: &[&str]) -> Keypad {
                 .enumerate()
                 .filter_map(|(x, c)| match c {
                     b'0'..=b'9' | b'A' | b'^' | b'<' | b'v' | b'>' => {
-                        Some(((x as i8, y as i8), c))
+                        Some((Point::new(x as i32, y as i32), c))
                     }
                     _ => None,
                 })
@@ -57,46 +64,29 @@ fn init_directional_keypad() -> Keypad {
     init_keypad(&[".^A", "<v>"])
 }
 
+/// The directional keys that move along `path`, followed by `A`.
+fn presses(path: &[Point]) -> String {
+    let dir = |a: Point, b: Point| {
+        Dir::ALL.into_iter().find(|d| d.delta() == b - a).unwrap()
+    };
+    path.windows(2)
+        .map(|w| dir(w[0], w[1]).to_char())
+        .chain(['A'])
+        .collect()
+}
+
 fn init_keyseqs(keypad: &Keypad) -> HashMap<(u8, u8), Vec<String>> {
     let mut sequences = HashMap::new();
     for (p, a) in keypad.iter() {
-        for b in keypad.values() {
-            let mut seqs: Vec<String> = Vec::new();
-            if a == b {
-                seqs.push("A".to_string());
-            } else {
-                let mut queue: VecDeque<((i8, i8), String)> = VecDeque::new();
-                queue.push_back((*p, String::new()));
-                let mut step = usize::MAX;
-                while let Some(((x, y), seq)) = queue.pop_front() {
-                    if seq.len() > step {
-                        continue;
-                    }
-                    if let Some(c) = keypad.get(&(x, y)) {
-                        if c == b {
-                            step = seq.len();
-                            let mut s = seq.clone();
-                            s.push('A');
-                            seqs.push(s);
-                        } else {
-                            [
-                                ((x, y - 1), '^'),
-                                ((x + 1, y), '>'),
-                                ((x, y + 1), 'v'),
-                                ((x - 1, y), '<'),
-                            ]
-                            .into_iter()
-                            .for_each(
-                                |(p, c)| {
-                                    let mut s = seq.clone();
-                                    s.push(c);
-                                    queue.push_back((p, s));
-                                },
-                            );
-                        }
-                    }
-                }
-            }
+        let keys =
+            |q: &Point| q.neighbours().filter(|q| keypad.contains_key(q));
+        let paths = search::bfs([*p], keys, |_| false);
+        for (q, b) in keypad.iter() {
+            let seqs: Vec<String> = paths
+                .all_paths(q)
+                .into_iter()
+                .map(|path| presses(&path))
+                .collect();
             let min = seqs
                 .iter()
                 .map(|s| {
@@ -186,15 +176,30 @@ fn compute_length(
     length
 }
 
-fn parse_input(input: &str) -> Vec<&str> {
-    input.trim().lines().collect()
+type Input = Vec<String>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day21::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|s| {
+            let digits = s.strip_suffix('A').unwrap_or_default();
+            match !digits.is_empty()
+                && digits.bytes().all(|c| c.is_ascii_digit())
+            {
+                true => Ok(s.to_string()),
+                false => Err(src.error(s, "expected a door code like 029A")),
+            }
+        })
+        .collect()
 }
 
-pub fn part_one(input: &str) -> usize {
+pub fn part_one(input: &Input) -> usize {
     let numseqs = NUM_SEQUENCES.get_or_init(init_numeric_seqs);
     let dirseqs = DIR_SEQUENCES.get_or_init(init_directional_seqs);
 
-    parse_input(input)
+    input
         .iter()
         .map(|s| "A".to_string() + s)
         .map(|s| {
@@ -213,12 +218,12 @@ pub fn part_one(input: &str) -> usize {
         .sum()
 }
 
-pub fn part_two(input: &str) -> usize {
+pub fn part_two(input: &Input) -> usize {
     let numseqs = NUM_SEQUENCES.get_or_init(init_numeric_seqs);
     let dirseqs = DIR_SEQUENCES.get_or_init(init_directional_seqs);
 
     let mut cache: HashMap<(u8, u8, usize), usize> = HashMap::new();
-    parse_input(input)
+    input
         .iter()
         .map(|s| {
             let s = "A".to_string() + s;
@@ -244,6 +249,28 @@ pub fn part_two(input: &str) -> usize {
         .sum()
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Keypad Conundrum";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,7 +278,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(21);
+        let input = parse_input(&read_example(21)).unwrap();
         assert_eq!(part_one(&input), 126384);
     }
 }
@@ -1,6 +1,11 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
 struct SecretNumber(i64);
 
 impl SecretNumber {
@@ -22,8 +27,14 @@ impl SecretNumber {
     }
 }
 
-fn parse_input(input: &str) -> Vec<i64> {
-    input.trim().lines().map(|s| s.parse().unwrap()).collect()
+type Input = Vec<i64>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day22::DAY, input);
+    if input.trim().is_empty() {
+        return Err(src.eof("no secret numbers"));
+    }
+    input.trim().lines().map(|s| src.number(s)).collect()
 }
 
 fn changes(list: Vec<i64>) -> Vec<(i64, i64)> {
@@ -33,10 +44,10 @@ fn changes(list: Vec<i64>) -> Vec<(i64, i64)> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> i64 {
-    parse_input(input)
-        .into_iter()
-        .map(|n| {
+pub fn part_one(input: &Input) -> i64 {
+    input
+        .iter()
+        .map(|&n| {
             let mut sn = SecretNumber(n);
             for _ in 0..2000 {
                 sn.next();
@@ -46,8 +57,8 @@ pub fn part_one(input: &str) -> i64 {
         .sum()
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let maps = parse_input(input)
+pub fn part_two(input: &Input) -> i64 {
+    let maps = input
         .iter()
         .map(|&n| {
             let mut sn = SecretNumber(n);
@@ -73,7 +84,30 @@ pub fn part_two(input: &str) -> i64 {
             }
         }
     }
-    *sums.values().max().unwrap()
+    // Without any buyers there are no bananas to be had.
+    sums.values().copied().max().unwrap_or(0)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Market";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -81,6 +115,13 @@ mod tests {
     use super::*;
     use crate::read_example;
 
+    #[test]
+    fn empty_input() {
+        let err = parse_input("\n\n").unwrap_err();
+        assert_eq!(err.message, "no secret numbers");
+        assert_eq!(part_two(&Vec::new()), 0);
+    }
+
     #[test]
     fn example_next_secret_number() {
         let expected = vec![
@@ -114,10 +155,10 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(22);
+        let input = parse_input(&read_example(22)).unwrap();
         assert_eq!(part_one(&input), 37327623);
 
-        let input = "1\n2\n3\n2024";
-        assert_eq!(part_two(input), 23);
+        let input = parse_input("1\n2\n3\n2024").unwrap();
+        assert_eq!(part_two(&input), 23);
     }
 }
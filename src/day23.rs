@@ -1,19 +1,28 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-fn parse_input(input: &str) -> Vec<(&str, &str)> {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<(String, String)>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day23::DAY, input);
     input
         .trim()
         .lines()
-        .map(|s| s.trim().splitn(2, '-').collect::<Vec<_>>())
-        .map(|v| (v[0], v[1]))
+        .map(|s| match s.trim().split_once('-') {
+            Some((a, b)) => Ok((a.to_string(), b.to_string())),
+            None => Err(src.error(s, "expected a connection like kh-tc")),
+        })
         .collect()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let input = parse_input(input);
+pub fn part_one(input: &Input) -> usize {
     let mut network: HashMap<&str, HashSet<&str>> = HashMap::new();
-    for &(a, b) in input.iter() {
+    for (a, b) in input.iter().map(|(a, b)| (a.as_str(), b.as_str())) {
         network
             .entry(a)
             .and_modify(|v| {
@@ -45,37 +54,75 @@ pub fn part_one(input: &str) -> usize {
     answers.len()
 }
 
-pub fn part_two(input: &str) -> String {
-    let input = parse_input(input);
-
-    let connected: HashSet<(&str, &str)> = input
-        .iter()
-        .flat_map(|&(a, b)| vec![(a, b), (b, a)])
-        .collect();
-    let nodes: HashSet<&str> =
-        input.iter().flat_map(|&(a, b)| vec![a, b]).collect();
-    let mut clusters: Vec<Vec<&str>> =
-        nodes.iter().map(|&node| vec![node]).collect();
-
-    for &a in nodes.iter() {
-        for cluster in clusters.iter_mut() {
-            if cluster.iter().all(|&b| connected.contains(&(a, b))) {
-                cluster.push(a);
-            }
+/// Bron–Kerbosch with pivoting: extends the clique `r` with nodes from
+/// `p`, skipping those in `x` already explored, keeping the largest clique
+/// found in `best`.
+fn largest_clique<'a>(
+    network: &HashMap<&'a str, HashSet<&'a str>>,
+    r: &mut Vec<&'a str>,
+    mut p: HashSet<&'a str>,
+    mut x: HashSet<&'a str>,
+    best: &mut Vec<&'a str>,
+) {
+    if p.is_empty() && x.is_empty() {
+        if r.len() > best.len() {
+            *best = r.clone();
         }
+        return;
     }
-    for cluster in clusters.iter_mut() {
-        cluster.sort_unstable();
+    let pivot = p.union(&x).max_by_key(|v| network[*v].len()).unwrap();
+    let candidates: Vec<&str> =
+        p.difference(&network[pivot]).copied().collect();
+    for v in candidates {
+        r.push(v);
+        let p2 = p.intersection(&network[v]).copied().collect();
+        let x2 = x.intersection(&network[v]).copied().collect();
+        largest_clique(network, r, p2, x2, best);
+        r.pop();
+        p.remove(v);
+        x.insert(v);
     }
-    let clusters = clusters
-        .into_iter()
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect::<Vec<_>>();
+}
+
+pub fn part_two(input: &Input) -> String {
+    let mut network: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (a, b) in input.iter().map(|(a, b)| (a.as_str(), b.as_str())) {
+        network.entry(a).or_default().insert(b);
+        network.entry(b).or_default().insert(a);
+    }
+    let nodes = network.keys().copied().collect();
+    let mut best = Vec::new();
+    largest_clique(
+        &network,
+        &mut Vec::new(),
+        nodes,
+        HashSet::new(),
+        &mut best,
+    );
+    best.sort_unstable();
+    best.join(",")
+}
+
+pub struct Day23;
 
-    let size = clusters.iter().map(|v| v.len()).max().unwrap();
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "LAN Party";
 
-    clusters.iter().find(|v| v.len() == size).unwrap().join(",")
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -85,8 +132,18 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(23);
+        let input = parse_input(&read_example(23)).unwrap();
         assert_eq!(part_one(&input), 7);
         assert_eq!(part_two(&input), "co,de,ka,ta");
     }
+
+    /// Part two used to grow clusters greedily in `HashSet` order and
+    /// now and then returned a smaller clique such as `tc,td,wh`.
+    #[test]
+    fn largest_clique_is_stable() {
+        let input = parse_input(&read_example(23)).unwrap();
+        for _ in 0..200 {
+            assert_eq!(part_two(&input), "co,de,ka,ta");
+        }
+    }
 }
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Gate {
+pub enum Gate {
     OR,
     AND,
     XOR,
@@ -22,19 +27,32 @@ impl Gate {
 type Knowns = HashMap<String, bool>;
 type Unknowns = HashMap<String, (String, String, Gate)>;
 
-fn parse_input(input: &str) -> (Knowns, Unknowns) {
+type Input = (Knowns, Unknowns);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day24::DAY, input);
     let mut knowns = Knowns::new();
     let mut unknowns = Unknowns::new();
-    let sections: Vec<&str> = input.trim().splitn(2, "\n\n").collect();
+    let (wires, gates) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| src.eof("missing blank line before the gates"))?;
 
-    for s in sections[0].lines() {
+    for s in wires.lines() {
         let v = s.split_ascii_whitespace().collect::<Vec<_>>();
-        let name = v[0][0..3].to_string();
-        let value = v[1] == "1";
-        knowns.insert(name, value);
+        let (name, value) = match v[..] {
+            [name, value] => (name.trim_end_matches(':'), value),
+            _ => return Err(src.error(s, "expected a wire like x00: 1")),
+        };
+        knowns.insert(name.to_string(), value == "1");
     }
-    for s in sections[1].lines() {
+    for s in gates.lines() {
         let v = s.split_ascii_whitespace().collect::<Vec<_>>();
+        if v.len() != 5 || v[3] != "->" {
+            return Err(
+                src.error(s, "expected a gate like x00 AND y00 -> z00")
+            );
+        }
         let name = v[4].to_string();
         let lhs = v[0].to_string();
         let rhs = v[2].to_string();
@@ -42,16 +60,16 @@ fn parse_input(input: &str) -> (Knowns, Unknowns) {
             "OR" => Gate::OR,
             "AND" => Gate::AND,
             "XOR" => Gate::XOR,
-            _ => panic!(),
+            op => return Err(src.error(op, format!("unknown gate {:?}", op))),
         };
         unknowns.insert(name, (lhs, rhs, gate));
     }
 
-    (knowns, unknowns)
+    Ok((knowns, unknowns))
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let (mut knowns, unknowns) = parse_input(input);
+pub fn part_one((knowns, unknowns): &Input) -> u64 {
+    let mut knowns = knowns.clone();
 
     fn get_value(
         name: &String,
@@ -76,7 +94,7 @@ pub fn part_one(input: &str) -> u64 {
         let mut vs = unknowns
             .keys()
             .filter(|it| it.starts_with('z'))
-            .map(|it| (it, get_value(it, &mut knowns, &unknowns)))
+            .map(|it| (it, get_value(it, &mut knowns, unknowns)))
             .collect::<Vec<_>>();
         vs.sort_unstable();
         vs.into_iter()
@@ -90,8 +108,21 @@ pub fn part_one(input: &str) -> u64 {
         .unwrap()
 }
 
-pub fn part_two(input: &str) -> String {
-    let (knowns, mut unknowns) = parse_input(input);
+/// The swapped output wires of a circuit meant to add `x` and `y` into
+/// `z`, or `None` if the circuit is not shaped like such an adder.
+pub fn part_two((knowns, unknowns): &Input) -> Option<String> {
+    let n = knowns.len() / 2;
+    let is_adder = (0..n).all(|i| {
+        knowns.contains_key(&format!("x{:02}", i))
+            && knowns.contains_key(&format!("y{:02}", i))
+    }) && (0..=n)
+        .all(|i| unknowns.contains_key(&format!("z{:02}", i)))
+        && !unknowns.contains_key(&format!("z{:02}", n + 1));
+    if knowns.len() % 2 == 1 || !is_adder {
+        return None;
+    }
+
+    let mut unknowns = unknowns.clone();
     unknowns.iter_mut().for_each(|(_, (lhs, rhs, _))| {
         if lhs > rhs {
             std::mem::swap(lhs, rhs);
@@ -104,7 +135,6 @@ pub fn part_two(input: &str) -> String {
     //              OR [x(i) AND carry(i-1)]
     //              OR [y(i) AND carry(i-1)]
 
-    let n = knowns.len() / 2;
     let sums: Vec<String> = (0..n)
         .map(|i| {
             unknowns
@@ -124,15 +154,14 @@ pub fn part_two(input: &str) -> String {
     #[allow(clippy::needless_range_loop)]
     for i in 0..n {
         let name = format!("z{:02}", i);
-        let (lhs, rhs, gate) = unknowns.get(&name).unwrap();
+        let (lhs, rhs, gate) = unknowns.get(&name)?;
         if gate != &Gate::XOR {
             let wire = unknowns
                 .iter()
                 .find(|(_, (lhs, rhs, gate))| {
                     gate == &Gate::XOR && (lhs == &sums[i] || rhs == &sums[i])
                 })
-                .map(|(name, _)| name.clone())
-                .unwrap();
+                .map(|(name, _)| name.clone())?;
             pairs.push([name, wire]);
             continue;
         }
@@ -145,9 +174,9 @@ pub fn part_two(input: &str) -> String {
             if let Some((lhs, rhs, gate)) = unknowns.get(name) {
                 if gate == &Gate::AND
                     && (lhs.starts_with('x') && rhs.starts_with('y'))
+                    && lhs[1..] == rhs[1..]
                 {
-                    assert!(lhs[1..] == rhs[1..]);
-                    v.insert(lhs[1..].parse().unwrap());
+                    v.extend(lhs[1..].parse::<usize>());
                 }
                 v.extend(get_carry_depends(lhs, unknowns));
                 v.extend(get_carry_depends(rhs, unknowns));
@@ -173,7 +202,29 @@ pub fn part_two(input: &str) -> String {
     // let mut v = ["z19", "mdd", "z37", "wts", "z11", "wpd", "jqf", "skh"];
     let mut v: Vec<String> = pairs.into_iter().flatten().collect();
     v.sort_unstable();
-    v.join(",")
+    Some(v.join(","))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Crossed Wires";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -181,9 +232,17 @@ mod tests {
     use super::*;
     use crate::read_example;
 
+    #[test]
+    fn unknown_gate() {
+        let err = parse_input("x00: 1\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+    }
+
     #[test]
     fn example() {
-        let input = read_example(24);
+        let input = parse_input(&read_example(24)).unwrap();
         assert_eq!(part_one(&input), 2024);
+        // The example is not an adder.
+        assert_eq!(part_two(&input), None);
     }
 }
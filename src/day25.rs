@@ -1,13 +1,27 @@
-fn parse_input(input: &str) -> Vec<Vec<Vec<char>>> {
+use crate::Answer;
+use crate::ParseError;
+use crate::Solution;
+use crate::Source;
+
+type Input = Vec<Vec<Vec<char>>>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let src = Source::new(Day25::DAY, input);
     input
         .trim()
         .split("\n\n")
-        .map(|section| section.lines().map(|s| s.chars().collect()).collect())
+        .map(|section| {
+            let grid: Vec<Vec<char>> =
+                section.lines().map(|s| s.chars().collect()).collect();
+            match grid.first() {
+                Some(row) if grid.len() > 1 && !row.is_empty() => Ok(grid),
+                _ => Err(src.error(section, "expected a lock or key")),
+            }
+        })
         .collect()
 }
 
-pub fn part_one(input: &str) -> usize {
-    let grids = parse_input(input);
+pub fn part_one(grids: &Input) -> usize {
     let pins: Vec<(bool, Vec<usize>)> = grids
         .iter()
         .map(|grid| {
@@ -52,8 +66,30 @@ pub fn part_one(input: &str) -> usize {
         .count()
 }
 
-pub fn part_two(_: &str) -> String {
-    String::new()
+pub fn part_two(_: &Input) -> Answer {
+    Answer::None
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Code Chronicle";
+
+    type Input = Input;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Input, _: &()) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Input, _: &()) -> Answer {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -63,7 +99,7 @@ mod tests {
 
     #[test]
     fn example() {
-        let input = read_example(25);
+        let input = parse_input(&read_example(25)).unwrap();
         assert_eq!(part_one(&input), 3);
     }
 }
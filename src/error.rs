@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A puzzle input that a day's parser rejected.
+///
+/// `line` and `column` are 1-based and point at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// The text a parser is working on, used to turn a slice of it back into
+/// a line and column for [`ParseError`].
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    day: u8,
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Source { day, input }
+    }
+
+    /// An error located at `at`, which should be a slice of the input.
+    /// Anything else is reported at the end of the input.
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let start = self.input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= self.input.len())
+            .unwrap_or(self.input.len());
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// An error for input that stops before the parser is done.
+    pub fn eof(&self, message: impl Into<String>) -> ParseError {
+        self.error(&self.input[self.input.len()..], message)
+    }
+
+    /// Parses `s` as a number, reporting failures at `s`.
+    pub fn number<T>(&self, s: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        s.parse().map_err(|e| {
+            self.error(s, format!("invalid number {:?}: {}", s, e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location() {
+        let input = "1 2\n3 x\n";
+        let src = Source::new(1, input);
+        let err = src.number::<i32>(&input[6..7]).unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (1, 2, 3));
+        assert_eq!(
+            err.to_string(),
+            "day 1, line 2, column 3: invalid number \"x\": \
+             invalid digit found in string"
+        );
+        let err = src.eof("unexpected end of input");
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
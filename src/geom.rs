@@ -0,0 +1,200 @@
+use std::fmt;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// A position or offset on a grid, with `y` growing downwards as in the
+/// puzzle maps.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ZERO: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    /// The point one step away in `dir`.
+    pub fn step(self, dir: Dir) -> Self {
+        self + dir.delta()
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The four points one step away, in [`Dir::ALL`] order.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Dir::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// The four [`Point::neighbours`] followed by the four diagonal points,
+    /// clockwise from the top right.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        const DIAGONAL: [Point; 4] = [
+            Point::new(1, -1),
+            Point::new(1, 1),
+            Point::new(-1, 1),
+            Point::new(-1, -1),
+        ];
+        self.neighbours().chain(DIAGONAL.map(|d| self + d))
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, k: i32) -> Point {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+/// Written as `x,y`, the way the puzzles print coordinates.
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// One of the four directions on a map, written `^`, `>`, `v` and `<`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// Clockwise from [`Dir::Up`].
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// The offset of one step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Dir::Up => Point::new(0, -1),
+            Dir::Right => Point::new(1, 0),
+            Dir::Down => Point::new(0, 1),
+            Dir::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Dir::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Dir::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Dir::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Reads one of `^>v<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Dir::Up),
+            '>' => Some(Dir::Right),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+}
+
+impl fmt::Display for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(-a * 2, Point::new(-2, -4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(Point::from((3, 4)).to_string(), "3,4");
+        assert_eq!(
+            Point::ZERO.neighbours().collect::<Vec<_>>(),
+            [(0, -1), (1, 0), (0, 1), (-1, 0)].map(Point::from)
+        );
+        assert_eq!(Point::ZERO.neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn dirs() {
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(Dir::Left.opposite(), Dir::Right);
+        assert_eq!(Point::new(2, 2).step(Dir::Down), Point::new(2, 3));
+        let s: String = "^>v<"
+            .chars()
+            .filter_map(Dir::from_char)
+            .map(Dir::to_char)
+            .collect();
+        assert_eq!(s, "^>v<");
+        assert_eq!(Dir::from_char('x'), None);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use crate::ParseError;
+use crate::Point;
+use crate::Source;
+
+/// A dense rectangular grid stored row by row. Points off the grid are
+/// rejected by [`Grid::get`] rather than wrapping around.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Reads one row per line of `text`, which should be a slice of the
+    /// input behind `src`, turning each character into a cell with
+    /// `cell`. Rows must all be as wide as the first, and characters that
+    /// `cell` rejects are reported where they occur.
+    pub fn parse(
+        src: &Source,
+        text: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = text.trim().lines().collect();
+        let width = match lines.first() {
+            Some(s) => s.chars().count(),
+            None => return Err(src.eof("empty grid")),
+        };
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for s in &lines {
+            if s.chars().count() != width {
+                return Err(src.error(s, format!("expected {} cells", width)));
+            }
+            for (i, c) in s.char_indices() {
+                match cell(c) {
+                    Some(v) => cells.push(v),
+                    None => {
+                        return Err(
+                            src.error(&s[i..], format!("unexpected {:?}", c))
+                        )
+                    }
+                }
+            }
+        }
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        let (x, y) = (usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?);
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.offset(p).is_some()
+    }
+
+    /// The cell at `p`, or `None` off the grid.
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// The [`Point::neighbours`] of `p` that are on the grid.
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours().filter(|&q| self.contains(q))
+    }
+
+    /// The [`Point::neighbours8`] of `p` that are on the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours8().filter(|&q| self.contains(q))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The first position holding `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(
+        &'a self,
+        value: &'a T,
+    ) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.offset(p) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                p, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        match self.offset(p) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                p, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// One line per row, with the cells written next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let input = "#.#\n.@.\n";
+        let grid = Grid::parse(&Source::new(1, input), input, Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'@'));
+        assert_eq!(grid[Point::new(2, 0)], '#');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.find(&'@'), Some(Point::new(1, 1)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(2, 0)]
+        );
+        assert_eq!(grid.to_string(), input);
+
+        let input = "##\n#\n";
+        let err =
+            Grid::parse(&Source::new(1, input), input, Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let input = "12\n3x\n";
+        let err =
+            Grid::parse(&Source::new(1, input), input, |c| c.to_digit(10))
+                .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "unexpected 'x'");
+        assert!(Grid::parse(&Source::new(1, ""), "", Some).is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[Point::new(1, 1)] = 5;
+        assert_eq!(
+            grid.neighbours(Point::new(0, 0)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours(Point::new(1, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.map(|v| v * 2).to_string(), "000\n0100\n");
+        assert_eq!(grid.positions().last(), Some(Point::new(2, 1)));
+    }
+}
@@ -0,0 +1,133 @@
+use std::io;
+use std::io::Write;
+
+use crate::Grid;
+use crate::Point;
+
+/// A colour as red, green and blue bytes.
+pub type Rgb = [u8; 3];
+
+/// A picture of a grid with one pixel per tile, written as a binary PGM
+/// when it has shades of gray and as a PPM when it has colours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Image {
+    Gray(Grid<u8>),
+    Rgb(Grid<Rgb>),
+}
+
+impl Image {
+    /// Colours each tile of `grid` with `palette`.
+    pub fn rgb<T>(grid: &Grid<T>, palette: impl Fn(&T) -> Rgb) -> Self {
+        Image::Rgb(grid.map(palette))
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Image::Gray(pixels) => pixels.width(),
+            Image::Rgb(pixels) => pixels.width(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            Image::Gray(pixels) => pixels.height(),
+            Image::Rgb(pixels) => pixels.height(),
+        }
+    }
+
+    /// The usual file extension, `pgm` or `ppm`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Image::Gray(_) => "pgm",
+            Image::Rgb(_) => "ppm",
+        }
+    }
+
+    /// Writes the binary PGM or PPM with each tile drawn as a `scale` by
+    /// `scale` square.
+    pub fn write(
+        &self,
+        out: &mut impl Write,
+        scale: usize,
+    ) -> io::Result<()> {
+        let (w, h) = (self.width() * scale, self.height() * scale);
+        let magic = match self {
+            Image::Gray(_) => "P5",
+            Image::Rgb(_) => "P6",
+        };
+        write!(out, "{}\n{} {}\n255\n", magic, w, h)?;
+        let mut line = Vec::new();
+        for y in 0..self.height() {
+            line.clear();
+            for x in 0..self.width() {
+                let p = Point::new(x as i32, y as i32);
+                for _ in 0..scale {
+                    match self {
+                        Image::Gray(pixels) => line.push(pixels[p]),
+                        Image::Rgb(pixels) => line.extend(pixels[p]),
+                    }
+                }
+            }
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The file contents [`Image::write`] produces.
+    pub fn to_bytes(&self, scale: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes, scale).unwrap();
+        bytes
+    }
+}
+
+/// The `i`th of a sequence of colours that keep neighbouring indices far
+/// apart, for telling many regions apart.
+pub fn distinct(i: usize) -> Rgb {
+    // Step the hue by the golden angle, at fixed saturation and value.
+    let hue = (i as f64 * 137.507_764) % 360.0 / 60.0;
+    let (s, v) = (0.65, 0.9);
+    let c = v * s;
+    let x = c * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    [r, g, b].map(|k| ((k + v - c) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let mut grid = Grid::new(2, 1, false);
+        grid[Point::new(1, 0)] = true;
+        let image = Image::Gray(grid.map(|&on| if on { 255 } else { 0 }));
+        assert_eq!(image.extension(), "pgm");
+        assert_eq!(image.to_bytes(1), b"P5\n2 1\n255\n\x00\xff");
+        assert_eq!(
+            image.to_bytes(2),
+            b"P5\n4 2\n255\n\x00\x00\xff\xff\x00\x00\xff\xff"
+        );
+
+        let image = Image::rgb(&grid, |&on| [on as u8, 2, 3]);
+        assert_eq!(
+            image.to_bytes(1),
+            b"P6\n2 1\n255\n\x00\x02\x03\x01\x02\x03"
+        );
+    }
+
+    #[test]
+    fn distinct_colors() {
+        assert_ne!(distinct(0), distinct(1));
+        assert_eq!(distinct(0), [230, 80, 80]);
+    }
+}
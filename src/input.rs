@@ -0,0 +1,183 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Why a puzzle input could not be loaded.
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        what: String,
+        searched: Vec<PathBuf>,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Malformed {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound { what, searched } => {
+                let paths: Vec<String> = searched
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                write!(
+                    f,
+                    "{} not found, looked in {}",
+                    what,
+                    paths.join(", ")
+                )
+            }
+            InputError::Io { path, source } => {
+                write!(f, "{}: {}", path.display(), source)
+            }
+            InputError::Malformed { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Finds `NN-<name>.txt` files in an ordered list of directories.
+///
+/// The search order is the `--input-dir` flag, then the `AOC_INPUT_DIR`
+/// environment variable, then the `inputs/` directory of this crate.
+/// Besides `NN-example.txt` a day can have any number of named examples
+/// in `NN-example-<name>.txt`.
+#[derive(Debug, Clone)]
+pub struct InputResolver {
+    dirs: Vec<PathBuf>,
+}
+
+impl InputResolver {
+    pub fn new(input_dir: Option<&Path>) -> Self {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        dirs.extend(input_dir.map(Path::to_path_buf));
+        dirs.extend(env::var_os("AOC_INPUT_DIR").map(PathBuf::from));
+        dirs.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+        InputResolver { dirs }
+    }
+
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    pub fn find(&self, day: u8, name: &str) -> Result<PathBuf, InputError> {
+        let filename = format!("{:02}-{}.txt", day, name);
+        self.find_file(&filename, &format!("{} for day {}", name, day))
+    }
+
+    /// Finds `filename` in the first search directory that has it;
+    /// `what` describes the file in the error.
+    pub fn find_file(
+        &self,
+        filename: &str,
+        what: &str,
+    ) -> Result<PathBuf, InputError> {
+        let searched: Vec<PathBuf> =
+            self.dirs.iter().map(|dir| dir.join(filename)).collect();
+        match searched.iter().find(|p| p.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(InputError::NotFound {
+                what: what.to_string(),
+                searched,
+            }),
+        }
+    }
+
+    pub fn read(&self, day: u8, name: &str) -> Result<String, InputError> {
+        read_path(&self.find(day, name)?)
+    }
+
+    /// The example variants of a day found in any search directory, such
+    /// as `example` and `example-oxo`, in sorted order.
+    pub fn examples(&self, day: u8) -> Vec<String> {
+        let prefix = format!("{:02}-", day);
+        let mut examples: Vec<String> = self
+            .dirs
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|filename| {
+                let variant = filename
+                    .strip_prefix(&prefix)?
+                    .strip_suffix(".txt")?
+                    .to_string();
+                let named = variant.strip_prefix("example-");
+                (variant == "example" || named.is_some_and(|s| !s.is_empty()))
+                    .then_some(variant)
+            })
+            .collect();
+        examples.sort();
+        examples.dedup();
+        examples
+    }
+}
+
+/// Reads an explicitly named input file, where `-` means stdin.
+pub fn read_path(path: &Path) -> Result<String, InputError> {
+    let io_error = |source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(io_error)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_order() {
+        let resolver = InputResolver::new(Some(Path::new("/nonexistent")));
+        assert_eq!(resolver.dirs()[0], Path::new("/nonexistent"));
+        let path = resolver.find(1, "example").unwrap();
+        assert!(path.ends_with("inputs/01-example.txt"));
+    }
+
+    #[test]
+    fn examples() {
+        let resolver = InputResolver::new(None);
+        assert_eq!(
+            resolver.examples(12),
+            ["example", "example-oxo", "example-small"]
+        );
+        assert_eq!(resolver.examples(1), ["example"]);
+    }
+
+    #[test]
+    fn not_found() {
+        let resolver = InputResolver::new(None);
+        let err = resolver.find(1, "missing").unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with("missing for day 1 not found, looked in"));
+        assert!(message.contains("01-missing.txt"));
+    }
+}
@@ -1,5 +1,3 @@
-use std::fs;
-
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -26,14 +24,169 @@ pub mod day23;
 pub mod day24;
 pub mod day25;
 
-pub fn read_as_string(day: u8, filename: &str) -> String {
-    let filename = format!("inputs/{:02}-{}.txt", day, filename);
-    fs::read_to_string(filename).unwrap()
+mod answer;
+mod answers;
+mod bench;
+mod cache;
+mod capi;
+mod error;
+mod geom;
+mod grid;
+mod image;
+mod input;
+mod mem;
+mod output;
+mod params;
+mod report;
+mod search;
+mod select;
+mod serve;
+mod solution;
+mod viz;
+mod watch;
+mod watchdog;
+
+pub use answer::Answer;
+pub use answers::Answers;
+pub use answers::Check;
+pub use bench::measure;
+pub use bench::BenchResults;
+pub use bench::Stats;
+pub use cache::input_hash;
+pub use cache::Cache;
+pub use capi::aoc_free;
+pub use capi::aoc_solve;
+pub use capi::aoc_titles;
+pub use capi::c_header;
+pub use error::ParseError;
+pub use error::Source;
+pub use geom::Dir;
+pub use geom::Point;
+pub use grid::Grid;
+pub use image::distinct;
+pub use image::Image;
+pub use image::Rgb;
+pub use input::read_path;
+pub use input::InputError;
+pub use input::InputResolver;
+pub use mem::track_memory;
+pub use mem::CountingAlloc;
+pub use mem::MemStats;
+pub use output::Format;
+pub use output::Record;
+pub use params::Params;
+pub use report::cpu_model;
+pub use report::results_block;
+pub use report::splice;
+pub use search::astar;
+pub use search::bfs;
+pub use search::dijkstra;
+pub use search::Paths;
+pub use select::Selection;
+pub use serve::handle;
+pub use serve::serve;
+pub use serve::Response;
+pub use solution::Parsed;
+pub use solution::Puzzle;
+pub use solution::Settings;
+pub use solution::Solution;
+pub use viz::dump;
+pub use viz::play;
+pub use viz::Color;
+pub use viz::Frame;
+pub use watch::diff;
+pub use watch::Watcher;
+pub use watchdog::supervise;
+pub use watchdog::Outcome;
+pub use watchdog::SOLVER_THREAD;
+
+static PUZZLES: [&dyn Puzzle; 25] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// Every registered day, in calendar order.
+pub fn puzzles() -> &'static [&'static dyn Puzzle] {
+    &PUZZLES
+}
+
+/// Looks up a registered day by its number.
+pub fn puzzle(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().find(|p| p.day() == day).copied()
+}
+
+pub fn read_as_string(day: u8, filename: &str) -> Result<String, InputError> {
+    InputResolver::new(None).read(day, filename)
 }
 
+/// Reads `NN-input.txt` for tests, panicking if it cannot be found.
 pub fn read_input(day: u8) -> String {
-    read_as_string(day, "input")
+    read_as_string(day, "input").unwrap_or_else(|e| panic!("{}", e))
 }
+
+/// Reads `NN-example.txt` for tests, panicking if it cannot be found.
 pub fn read_example(day: u8) -> String {
-    read_as_string(day, "example")
+    read_as_string(day, "example").unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Reads `NN-example-<name>.txt` for tests, panicking if it cannot be
+/// found.
+pub fn read_named_example(day: u8, name: &str) -> String {
+    read_as_string(day, &format!("example-{}", name))
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        let days: Vec<u8> = puzzles().iter().map(|p| p.day()).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+        assert_eq!(puzzle(6).unwrap().title(), "Guard Gallivant");
+        assert!(puzzle(26).is_none());
+    }
+
+    #[test]
+    fn parse_once() {
+        let puzzle = puzzle(1).unwrap();
+        let parsed = puzzle.parse(&read_example(1)).unwrap();
+        let settings = puzzle.settings("example", &[]).unwrap();
+        assert_eq!(parsed.day(), 1);
+        assert_eq!(puzzle.solve(&parsed, &settings, 1), Answer::from(11));
+        assert_eq!(puzzle.solve(&parsed, &settings, 2), Answer::from(31));
+    }
+
+    #[test]
+    #[should_panic(expected = "day 1 has no part 3")]
+    fn unknown_part() {
+        let puzzle = puzzle(1).unwrap();
+        let parsed = puzzle.parse(&read_example(1)).unwrap();
+        let settings = puzzle.settings("example", &[]).unwrap();
+        puzzle.solve(&parsed, &settings, 3);
+    }
 }
@@ -1,79 +1,884 @@
+use std::collections::BTreeMap;
 use std::env;
-use std::fmt::Display;
-use std::time::SystemTime;
+use std::fmt::Write;
+use std::fs;
+use std::hint;
+use std::net::Ipv4Addr;
+use std::net::TcpListener;
+use std::panic;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-fn main() {
-    macro_rules! puzzle {
-        ($mod:ident, $title:expr) => {
-            (
-                $title,
-                |input| Box::new(aoc::$mod::part_one(input)),
-                |input| Box::new(aoc::$mod::part_two(input)),
-            )
+use aoc::supervise;
+use aoc::Answer;
+use aoc::Answers;
+use aoc::BenchResults;
+use aoc::Cache;
+use aoc::Check;
+use aoc::CountingAlloc;
+use aoc::Format;
+use aoc::InputError;
+use aoc::InputResolver;
+use aoc::Outcome;
+use aoc::Parsed;
+use aoc::Puzzle;
+use aoc::Record;
+use aoc::Selection;
+use aoc::Settings;
+use aoc::Watcher;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[derive(PartialEq)]
+enum CacheMode {
+    Off,
+    Read,
+    Refresh,
+}
+
+enum Command {
+    Run,
+    Bench,
+    Report,
+    Watch,
+    Serve,
+    Viz,
+    Image,
+}
+
+/// How often `aoc watch` looks at the input files.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+struct Args {
+    command: Command,
+    selection: Selection,
+    variant: String,
+    show_time: bool,
+    mem: bool,
+    cache: CacheMode,
+    check: bool,
+    format: Format,
+    jobs: usize,
+    timeout: Option<Duration>,
+    params: Vec<(String, String)>,
+    settings: BTreeMap<u8, Arc<Settings>>,
+    input_dir: Option<PathBuf>,
+    input: Option<PathBuf>,
+    warmup: usize,
+    iterations: usize,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    threshold: f64,
+    readme: PathBuf,
+    port: u16,
+    delay: Duration,
+    step: bool,
+    out: Option<PathBuf>,
+    scale: usize,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        command: Command::Run,
+        selection: Selection::default(),
+        variant: "input".to_string(),
+        show_time: false,
+        mem: false,
+        cache: CacheMode::Off,
+        check: false,
+        format: Format::Text,
+        jobs: 1,
+        timeout: None,
+        params: Vec::new(),
+        settings: BTreeMap::new(),
+        input_dir: None,
+        input: None,
+        warmup: 3,
+        iterations: 10,
+        save: None,
+        compare: None,
+        threshold: 10.0,
+        readme: Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md"),
+        port: 8080,
+        delay: Duration::from_millis(100),
+        step: false,
+        out: None,
+        scale: 4,
+    };
+    fn number<T: FromStr>(flag: &str, value: String) -> Result<T, String> {
+        value.parse().map_err(|_| {
+            format!("{} expects a number, got {:?}", flag, value)
+        })
+    }
+    let mut iter = env::args().skip(1).peekable();
+    match iter.peek().map(|s| s.as_str()) {
+        Some("bench") => args.command = Command::Bench,
+        Some("watch") => args.command = Command::Watch,
+        Some("serve") => args.command = Command::Serve,
+        Some("viz") => args.command = Command::Viz,
+        Some("image") => args.command = Command::Image,
+        Some("report") => {
+            args.command = Command::Report;
+            args.show_time = true;
+        }
+        _ => {}
+    }
+    if !matches!(args.command, Command::Run) {
+        iter.next();
+    }
+    while let Some(arg) = iter.next() {
+        let mut value =
+            || iter.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--example" => {
+                // A following argument that is not a day names an example.
+                let name = iter.next_if(|s| {
+                    !s.starts_with('-')
+                        && Selection::default().add(s).is_err()
+                });
+                args.variant = match name {
+                    Some(name) => format!("example-{}", name),
+                    None => "example".to_string(),
+                };
+            }
+            "--time" => args.show_time = true,
+            "--mem" => args.mem = true,
+            "--cached" if args.cache == CacheMode::Off => {
+                args.cache = CacheMode::Read
+            }
+            "--cached" => {}
+            "--no-cache" => args.cache = CacheMode::Refresh,
+            "--check" => args.check = true,
+            "--format" => args.format = value()?.parse()?,
+            "--jobs" => args.jobs = number(&arg, value()?)?,
+            "--timeout" => {
+                let secs: f64 = number(&arg, value()?)?;
+                args.timeout = Duration::try_from_secs_f64(secs).ok();
+                if args.timeout.is_none() {
+                    return Err(format!("invalid --timeout {}", secs));
+                }
+            }
+            "--param" => {
+                let param = value()?;
+                let Some((key, value)) = param.split_once('=') else {
+                    return Err(format!(
+                        "--param expects key=value, got {:?}",
+                        param
+                    ));
+                };
+                args.params.push((key.to_string(), value.to_string()));
+            }
+            "--input-dir" => args.input_dir = Some(value()?.into()),
+            "--input" => args.input = Some(value()?.into()),
+            "--warmup" => args.warmup = number(&arg, value()?)?,
+            "--iterations" => args.iterations = number(&arg, value()?)?,
+            "--save" => args.save = Some(value()?.into()),
+            "--compare" => args.compare = Some(value()?.into()),
+            "--readme" => args.readme = value()?.into(),
+            "--threshold" => args.threshold = number(&arg, value()?)?,
+            "--port" => args.port = number(&arg, value()?)?,
+            "--delay" => {
+                args.delay = Duration::from_millis(number(&arg, value()?)?)
+            }
+            "--step" => args.step = true,
+            "--out" => args.out = Some(value()?.into()),
+            "--scale" => args.scale = number(&arg, value()?)?,
+            s if !s.starts_with('-') => args.selection.add(s)?,
+            s => return Err(format!("unknown argument {:?}", s)),
+        }
+    }
+    if matches!(args.command, Command::Report) && !args.selection.is_empty() {
+        return Err("report always covers every day".to_string());
+    }
+    if args.selection.is_empty() {
+        args.selection = Selection::all();
+    }
+    if args.jobs == 0 {
+        return Err("--jobs needs at least one thread".to_string());
+    }
+    if matches!(args.command, Command::Bench) && args.jobs != 1 {
+        return Err("bench times one day at a time; --jobs is not supported"
+            .to_string());
+    }
+    if args.input.is_some() && args.selection.days().count() != 1 {
+        return Err("--input needs exactly one day".to_string());
+    }
+    if matches!(args.command, Command::Viz | Command::Image)
+        && args.selection.days().count() != 1
+    {
+        return Err("viz and image need exactly one day".to_string());
+    }
+    if args.scale == 0 {
+        return Err("--scale needs to be at least 1".to_string());
+    }
+    for day in args.selection.days() {
+        let puzzle = aoc::puzzle(day).unwrap();
+        let settings = puzzle
+            .settings(&args.variant, &args.params)
+            .map_err(|e| format!("day {}: {}", day, e))?;
+        args.settings.insert(day, Arc::new(settings));
+    }
+    Ok(args)
+}
+
+fn load_input(
+    args: &Args,
+    resolver: &InputResolver,
+    day: u8,
+) -> Result<String, InputError> {
+    match &args.input {
+        Some(path) => aoc::read_path(path),
+        None => resolver.read(day, &args.variant),
+    }
+}
+
+/// Everything one day produced, held back until the days before it have
+/// been printed.
+#[derive(Default)]
+struct Report {
+    text: String,
+    errors: Vec<String>,
+    records: Vec<Record>,
+    failed: bool,
+    /// The cache key of the day's input, variant and parameters.
+    hash: u64,
+}
+
+impl Report {
+    /// Checks and prints one answer and keeps its record.
+    fn answer(
+        &mut self,
+        args: &Args,
+        answers: &Answers,
+        record: Record,
+        note: &str,
+    ) {
+        let name = ["One", "Two"][record.part as usize - 1];
+        let check = match args.check {
+            true => {
+                let check = answers.check(
+                    record.day,
+                    &args.variant,
+                    record.part,
+                    &record.answer,
+                );
+                self.failed |= matches!(check, Check::Fail { .. });
+                format!(" {}", check)
+            }
+            false => String::new(),
         };
+        let _ = writeln!(
+            self.text,
+            "Part {}: {}{}{}",
+            name, record.answer, check, note
+        );
+        self.records.push(record);
     }
 
-    type SolverFn = fn(&str) -> Box<dyn Display>;
-
-    let puzzles: Vec<(&str, SolverFn, SolverFn)> = vec![
-        puzzle!(day01, "Historian Hysteria"),
-        puzzle!(day02, "Red-Nosed Reports"),
-        puzzle!(day03, "Mull It Over"),
-        puzzle!(day04, "Ceres Search"),
-        puzzle!(day05, "Print Queue"),
-        puzzle!(day06, "Guard Gallivant"),
-        puzzle!(day07, "Bridge Repair"),
-        puzzle!(day08, "Resonant Collinearity"),
-        puzzle!(day09, "Disk Fragmenter"),
-        puzzle!(day10, "Hoof It"),
-        puzzle!(day11, "Plutonian Pebbles"),
-        puzzle!(day12, "Garden Groups"),
-        puzzle!(day13, "Claw Contraption"),
-        puzzle!(day14, "Restroom Redoubt"),
-        puzzle!(day15, "Warehouse Woes"),
-        puzzle!(day16, "Reindeer Maze"),
-        puzzle!(day17, "Chronospatial Computer"),
-        puzzle!(day18, "RAM Run"),
-        puzzle!(day19, "Linen Layout"),
-        puzzle!(day20, "Race Condition"),
-        puzzle!(day21, "Keypad Conundrum"),
-        puzzle!(day22, "Monkey Market"),
-        puzzle!(day23, "LAN Party"),
-        puzzle!(day24, "Crossed Wires"),
-        puzzle!(day25, "Code Chronicle "),
-    ];
-
-    let filename = match env::args().find(|a| a == "--example") {
-        None => "input",
-        Some(_) => "example",
+    /// Answers every selected part from the cache, if it has them all.
+    fn cached(
+        &mut self,
+        args: &Args,
+        answers: &Answers,
+        cache: &Cache,
+        puzzle: &dyn Puzzle,
+    ) -> bool {
+        let day = puzzle.day();
+        let parts: Vec<u8> = [0, 1, 2]
+            .into_iter()
+            .filter(|&part| part == 0 || args.selection.contains(day, part))
+            .collect();
+        let hits: Option<Vec<&(Answer, Duration)>> = parts
+            .iter()
+            .map(|&part| cache.get(day, part, self.hash))
+            .collect();
+        let Some(hits) = hits else {
+            return false;
+        };
+
+        let _ =
+            writeln!(self.text, "--- Day {}: {} ---", day, puzzle.title());
+        let parse = hits[0].1;
+        let mut times = vec![format!("parse {:?}", parse)];
+        for (&part, (answer, time)) in parts.iter().zip(hits).skip(1) {
+            let name = ["one", "two"][part as usize - 1];
+            times.push(format!("part {} {:?}", name, time));
+            let record = Record {
+                day,
+                title: puzzle.title(),
+                part,
+                answer: answer.clone(),
+                parse,
+                time: *time,
+            };
+            self.answer(args, answers, record, " (cached)");
+        }
+        if args.show_time {
+            let _ = writeln!(self.text, "Duration: {}", times.join(", "));
+        }
+        self.text.push('\n');
+        true
+    }
+}
+
+/// Solves the selected parts of one day.
+fn run_day(
+    args: &Args,
+    resolver: &InputResolver,
+    answers: &Answers,
+    cache: &Cache,
+    day: u8,
+) -> Report {
+    let mut report = Report::default();
+    let puzzle = aoc::puzzle(day).unwrap();
+    let input = match load_input(args, resolver, day) {
+        Ok(input) => input,
+        Err(e) => {
+            report.errors.push(e.to_string());
+            report.failed = true;
+            return report;
+        }
+    };
+    let params: Vec<String> = args
+        .params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    report.hash =
+        aoc::input_hash(&[&input, &args.variant, &params.join(" ")]);
+    if args.cache == CacheMode::Read
+        && report.cached(args, answers, cache, puzzle)
+    {
+        return report;
+    }
+
+    let _ = writeln!(report.text, "--- Day {}: {} ---", day, puzzle.title());
+    let parsed = match supervise(args.timeout, move || {
+        aoc::track_memory(|| puzzle.parse(&input))
+    }) {
+        Outcome::Done {
+            value: (Ok(parsed), mem),
+            time,
+        } => (Arc::new(parsed), time, mem),
+        Outcome::Done {
+            value: (Err(e), _), ..
+        } => {
+            report.errors.push(e.to_string());
+            report.failed = true;
+            return report;
+        }
+        outcome => {
+            let _ = writeln!(report.text, "Parse: {}\n", outcome);
+            report
+                .errors
+                .push(format!("day {} parse: {}", day, outcome));
+            report.failed = true;
+            return report;
+        }
+    };
+    let (parsed, parse, mem) = parsed;
+    let mut times = vec![format!("parse {:?}", parse)];
+    let mut mems = vec![format!("parse {}", mem)];
+    for (part, name) in [(1, "One"), (2, "Two")] {
+        if !args.selection.contains(day, part) {
+            continue;
+        }
+        let shared = Arc::clone(&parsed);
+        let settings = Arc::clone(&args.settings[&day]);
+        let (answer, time, mem) = match supervise(args.timeout, move || {
+            aoc::track_memory(|| puzzle.solve(&shared, &settings, part))
+        }) {
+            Outcome::Done {
+                value: (answer, mem),
+                time,
+            } => (answer, time, mem),
+            outcome => {
+                let _ = writeln!(report.text, "Part {}: {}", name, outcome);
+                report
+                    .errors
+                    .push(format!("day {} part {}: {}", day, part, outcome));
+                report.failed = true;
+                continue;
+            }
+        };
+        times.push(format!("part {} {:?}", name.to_lowercase(), time));
+        mems.push(format!("part {} {}", name.to_lowercase(), mem));
+        let record = Record {
+            day,
+            title: puzzle.title(),
+            part,
+            answer,
+            parse,
+            time,
+        };
+        report.answer(args, answers, record, "");
+    }
+    if args.show_time {
+        let _ = writeln!(report.text, "Duration: {}", times.join(", "));
+    }
+    if args.mem {
+        let _ = writeln!(report.text, "Memory: {}", mems.join("; "));
+    }
+    report.text.push('\n');
+    report
+}
+
+/// Solves each selected day on `--jobs` threads, handing the reports to
+/// `each` in day order.
+fn solve_days(
+    args: &Args,
+    resolver: &InputResolver,
+    answers: &Answers,
+    cache: &Cache,
+    mut each: impl FnMut(Report),
+) {
+    let days: Vec<u8> = args.selection.days().collect();
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        let (days, next) = (&days, &next);
+        for _ in 0..args.jobs.min(days.len()) {
+            let tx = tx.clone();
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                let report = run_day(args, resolver, answers, cache, day);
+                if tx.send((i, report)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut done = 0;
+        for (i, report) in rx {
+            pending.insert(i, report);
+            while let Some(report) = pending.remove(&done) {
+                each(report);
+                done += 1;
+            }
+        }
+    });
+}
+
+/// Solves each day once, printing the answers. Returns whether anything
+/// failed.
+fn run(args: &Args, resolver: &InputResolver) -> bool {
+    let answers = match args.check {
+        true => Answers::load(resolver).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }),
+        false => Answers::default(),
     };
 
-    let show_time = env::args().any(|a| a == "--time");
+    let path = Cache::default_path();
+    let mut cache = match args.cache {
+        CacheMode::Off => Cache::default(),
+        _ => Cache::load(&path),
+    };
 
-    let mut days: Vec<usize> =
-        env::args().filter_map(|a| a.parse().ok()).collect();
+    let mut records = Vec::new();
+    let mut failed = false;
+    let mut hashes = BTreeMap::new();
+    solve_days(args, resolver, &answers, &cache, |report| {
+        if args.format == Format::Text {
+            print!("{}", report.text);
+        }
+        for e in &report.errors {
+            eprintln!("error: {}", e);
+        }
+        if let Some(record) = report.records.first() {
+            hashes.insert(record.day, report.hash);
+        }
+        records.extend(report.records);
+        failed |= report.failed;
+    });
+    print!("{}", args.format.render(&records));
 
-    if days.is_empty() {
-        days = (1..=puzzles.len()).collect();
+    if args.cache != CacheMode::Off {
+        for r in &records {
+            let hash = hashes[&r.day];
+            cache.insert(r.day, 0, hash, Answer::None, r.parse);
+            cache.insert(r.day, r.part, hash, r.answer.clone(), r.time);
+        }
+        if let Err(e) = cache.save(&path) {
+            eprintln!("error: {}: {}", path.display(), e);
+        }
+    }
+    failed
+}
+
+/// Solves every day and rewrites the results block of the README. The
+/// README is left alone if anything failed.
+fn report(args: &Args, resolver: &InputResolver) -> bool {
+    let mut output = String::new();
+    let mut failed = false;
+    let (answers, cache) = (Answers::default(), Cache::default());
+    solve_days(args, resolver, &answers, &cache, |report| {
+        for e in &report.errors {
+            eprintln!("error: {}", e);
+        }
+        output += &report.text;
+        failed |= report.failed;
+    });
+    if failed {
+        return true;
+    }
+
+    let block = aoc::results_block(&output, aoc::cpu_model().as_deref());
+    let written = fs::read_to_string(&args.readme)
+        .map_err(|e| e.to_string())
+        .and_then(|text| aoc::splice(&text, &block))
+        .and_then(|text| {
+            fs::write(&args.readme, text).map_err(|e| e.to_string())
+        });
+    match written {
+        Ok(()) => {
+            println!("updated {}", args.readme.display());
+            false
+        }
+        Err(e) => {
+            eprintln!("error: {}: {}", args.readme.display(), e);
+            true
+        }
+    }
+}
+
+/// Reruns the selected days whenever one of their input files changes,
+/// showing which answers moved. Runs until interrupted.
+fn watch(args: &Args, resolver: &InputResolver) -> bool {
+    let answers = match args.check {
+        true => Answers::load(resolver).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }),
+        false => Answers::default(),
+    };
+    let paths: Result<Vec<PathBuf>, InputError> = match &args.input {
+        Some(path) => Ok(vec![path.clone()]),
+        None => args
+            .selection
+            .days()
+            .map(|day| resolver.find(day, &args.variant))
+            .collect(),
+    };
+    let mut watcher = Watcher::new(paths.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    }));
+
+    let mut previous: Vec<Record> = Vec::new();
+    loop {
+        let mut records = Vec::new();
+        solve_days(args, resolver, &answers, &Cache::default(), |report| {
+            print!("{}", report.text);
+            for e in &report.errors {
+                eprintln!("error: {}", e);
+            }
+            records.extend(report.records);
+        });
+        if !previous.is_empty() {
+            let changes = aoc::diff(&previous, &records);
+            match changes.is_empty() {
+                true => println!("no answers changed"),
+                false => println!("changed:\n  {}", changes.join("\n  ")),
+            }
+            println!();
+        }
+        previous = records;
+
+        let paths: Vec<String> =
+            watcher.paths().map(|p| p.display().to_string()).collect();
+        println!("watching {}", paths.join(", "));
+        while !watcher.changed() {
+            thread::sleep(POLL_INTERVAL);
+        }
+        println!();
     }
+}
 
-    for day in days {
-        let (title, part1, part2) = &puzzles[day - 1];
-        let input = aoc::read_as_string(day as u8, filename);
-        let input = input.as_str();
+/// Times parsing and each part repeatedly, optionally saving the results and
+/// comparing them with an earlier run. `--timeout` bounds parsing and each
+/// part's whole series of runs. Returns whether anything failed or regressed
+/// beyond the threshold.
+fn bench(args: &Args, resolver: &InputResolver) -> bool {
+    let previous = args.compare.as_ref().map(|path| {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| BenchResults::parse(&text))
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}: {}", path.display(), e);
+                process::exit(2);
+            })
+    });
 
-        println!("--- Day {}: {} ---", day, title);
-        let t0 = SystemTime::now();
-        println!("Part One: {}", part1(input));
-        let t1 = SystemTime::now();
-        println!("Part Two: {}", part2(input));
-        let t2 = SystemTime::now();
+    let mut failed = false;
+    let mut results = BenchResults::default();
+    for day in args.selection.days() {
+        let puzzle = aoc::puzzle(day).unwrap();
+        let input = match load_input(args, resolver, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed = true;
+                continue;
+            }
+        };
 
-        if show_time {
-            let d1 = t1.duration_since(t0).unwrap_or_default();
-            let d2 = t2.duration_since(t1).unwrap_or_default();
-            println!("Duration: {:?}", (d1, d2));
+        println!("--- Day {}: {} ---", day, puzzle.title());
+        let input: Arc<str> = input.into();
+        let shared = Arc::clone(&input);
+        let outcome = supervise(args.timeout, move || puzzle.parse(&shared));
+        let parsed = match outcome {
+            Outcome::Done {
+                value: Ok(parsed), ..
+            } => Arc::new(parsed),
+            Outcome::Done { value: Err(e), .. } => {
+                eprintln!("error: {}", e);
+                failed = true;
+                continue;
+            }
+            outcome => {
+                println!("Parse: {}\n", outcome);
+                failed = true;
+                continue;
+            }
+        };
+        for (part, name) in [(0, "Parse"), (1, "Part One"), (2, "Part Two")] {
+            if part > 0 && !args.selection.contains(day, part) {
+                continue;
+            }
+            let (warmup, iterations) = (args.warmup, args.iterations);
+            let (input, parsed) = (Arc::clone(&input), Arc::clone(&parsed));
+            let settings = Arc::clone(&args.settings[&day]);
+            let stats = supervise(args.timeout, move || match part {
+                0 => aoc::measure(warmup, iterations, || {
+                    hint::black_box(puzzle.parse(&input)).ok();
+                }),
+                _ => aoc::measure(warmup, iterations, || {
+                    hint::black_box(puzzle.solve(&parsed, &settings, part));
+                }),
+            });
+            let stats = match stats {
+                Outcome::Done { value, .. } => value,
+                outcome => {
+                    println!("{}: {}", name, outcome);
+                    failed = true;
+                    continue;
+                }
+            };
+            results.insert(day, part, stats);
+            let change = previous
+                .as_ref()
+                .and_then(|previous| previous.get(day, part))
+                .map(|old| stats.change_since(old) * 100.0);
+            match change {
+                Some(change) if change > args.threshold => {
+                    failed = true;
+                    println!(
+                        "{}: {}  {:+.1}% REGRESSION",
+                        name, stats, change
+                    );
+                }
+                Some(change) => {
+                    println!("{}: {}  {:+.1}%", name, stats, change)
+                }
+                None => println!("{}: {}", name, stats),
+            }
         }
         println!();
     }
+
+    if let Some(path) = &args.save {
+        if let Err(e) = fs::write(path, results.to_text()) {
+            eprintln!("error: {}: {}", path.display(), e);
+            failed = true;
+        }
+    }
+    failed
+}
+
+/// Answers HTTP requests on localhost until interrupted. Only fails if the
+/// port cannot be bound.
+fn serve(args: &Args) -> bool {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, args.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: port {}: {}", args.port, e);
+            return true;
+        }
+    };
+    println!("listening on http://{}", listener.local_addr().unwrap());
+    aoc::serve(listener, args.timeout);
+    true
+}
+
+/// Loads and parses the input for `puzzle` under supervision, reporting
+/// any error.
+fn parse_one(
+    args: &Args,
+    resolver: &InputResolver,
+    puzzle: &'static dyn Puzzle,
+) -> Option<Arc<Parsed>> {
+    let day = puzzle.day();
+    let input = match load_input(args, resolver, day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return None;
+        }
+    };
+    match supervise(args.timeout, move || puzzle.parse(&input)) {
+        Outcome::Done {
+            value: Ok(parsed), ..
+        } => Some(Arc::new(parsed)),
+        Outcome::Done { value: Err(e), .. } => {
+            eprintln!("error: {}", e);
+            None
+        }
+        outcome => {
+            eprintln!("error: day {} parse: {}", day, outcome);
+            None
+        }
+    }
+}
+
+/// Plays the frames of the selected day in the terminal, or writes them to
+/// `--out`. Fails if the day cannot be parsed or has nothing to show.
+///
+/// The frames are produced on a supervised thread, so a solver panic is
+/// reported as FAILED, and handed over one at a time as they are shown.
+fn viz(args: &Args, resolver: &InputResolver) -> bool {
+    let day = args.selection.days().next().unwrap();
+    let puzzle = aoc::puzzle(day).unwrap();
+    let Some(parsed) = parse_one(args, resolver, puzzle) else {
+        return true;
+    };
+    let settings = Arc::clone(&args.settings[&day]);
+    let (tx, rx) = mpsc::sync_channel(16);
+    let (outcome, shown) = thread::scope(|s| {
+        let shown = s.spawn(|| match &args.out {
+            Some(dir) => aoc::dump(rx.into_iter(), dir),
+            None => aoc::play(rx.into_iter(), args.delay, args.step),
+        });
+        // Playback can take as long as it likes, so there is no timeout.
+        let outcome = supervise(None, move || {
+            for frame in puzzle.frames(&parsed, &settings) {
+                if tx.send(frame).is_err() {
+                    break;
+                }
+            }
+        });
+        (outcome, shown.join().unwrap())
+    });
+    if !matches!(outcome, Outcome::Done { .. }) {
+        eprintln!("error: day {} frames: {}", day, outcome);
+        return true;
+    }
+    match (shown, &args.out) {
+        (Ok(0), _) => {
+            eprintln!("error: day {} has no visualization", day);
+            true
+        }
+        (Ok(count), Some(dir)) => {
+            println!("wrote {} frames to {}", count, dir.display());
+            false
+        }
+        (Ok(_), None) => false,
+        (Err(e), _) => {
+            eprintln!("error: {}", e);
+            true
+        }
+    }
+}
+
+/// Writes the picture of the selected day to `--out`, or to `dayNN.ppm`
+/// or `.pgm` in the current directory.
+fn image(args: &Args, resolver: &InputResolver) -> bool {
+    let day = args.selection.days().next().unwrap();
+    let puzzle = aoc::puzzle(day).unwrap();
+    let Some(parsed) = parse_one(args, resolver, puzzle) else {
+        return true;
+    };
+    let settings = Arc::clone(&args.settings[&day]);
+    let image = match supervise(args.timeout, move || {
+        puzzle.image(&parsed, &settings)
+    }) {
+        Outcome::Done {
+            value: Some(image), ..
+        } => image,
+        Outcome::Done { value: None, .. } => {
+            eprintln!("error: day {} has no image", day);
+            return true;
+        }
+        outcome => {
+            eprintln!("error: day {} image: {}", day, outcome);
+            return true;
+        }
+    };
+    let path = match &args.out {
+        Some(path) => path.clone(),
+        None => format!("day{:02}.{}", day, image.extension()).into(),
+    };
+    match fs::write(&path, image.to_bytes(args.scale)) {
+        Ok(()) => {
+            println!(
+                "wrote {}x{} image to {}",
+                image.width() * args.scale,
+                image.height() * args.scale,
+                path.display()
+            );
+            false
+        }
+        Err(e) => {
+            eprintln!("error: {}: {}", path.display(), e);
+            true
+        }
+    }
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    let resolver = InputResolver::new(args.input_dir.as_deref());
+    if args.mem {
+        CountingAlloc::enable();
+    }
+
+    // Solver panics are reported as FAILED, so keep their messages off
+    // stderr.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(aoc::SOLVER_THREAD) {
+            default_hook(info);
+        }
+    }));
+
+    let failed = match args.command {
+        Command::Run => run(&args, &resolver),
+        Command::Bench => bench(&args, &resolver),
+        Command::Report => report(&args, &resolver),
+        Command::Watch => watch(&args, &resolver),
+        Command::Serve => serve(&args),
+        Command::Viz => viz(&args, &resolver),
+        Command::Image => image(&args, &resolver),
+    };
+    if failed {
+        process::exit(1);
+    }
 }
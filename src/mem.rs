@@ -0,0 +1,186 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Allocation counts of one piece of work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The most bytes live at once, above what was live at the start.
+    pub peak: u64,
+}
+
+impl fmt::Display for MemStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let units = ["B", "KiB", "MiB", "GiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit + 1 < units.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} B", self.0),
+            _ => write!(f, "{:.1} {}", value, units[unit]),
+        }
+    }
+}
+
+/// A global allocator that counts allocations per thread once
+/// [`CountingAlloc::enable`] has been called. The binary installs it; the
+/// counting is only switched on for `--mem`.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    pub fn enable() {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    fn record(allocated: usize, freed: usize) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            if allocated > 0 {
+                c.allocations += 1;
+                c.bytes += allocated as u64;
+            }
+            c.live += allocated as i64 - freed as i64;
+            c.peak = c.peak.max(c.live);
+            counters.set(c);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAlloc::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAlloc::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAlloc::record(0, layout.size());
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            CountingAlloc::record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+/// Runs `f` and returns what it allocated on the current thread. The
+/// counts stay zero unless [`CountingAlloc`] is the global allocator and
+/// has been enabled.
+pub fn track_memory<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+    let start = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.live;
+        counters.set(c);
+        c
+    });
+    let value = f();
+    let end = COUNTERS.with(Cell::get);
+    let stats = MemStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live).max(0) as u64,
+    };
+    (value, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn counts() {
+        CountingAlloc::enable();
+        let (_, stats) = track_memory(|| {
+            let small = vec![0u8; 1000];
+            drop(small);
+            let mut v: Vec<u8> = Vec::with_capacity(4000);
+            v.resize(8000, 1);
+            v.len()
+        });
+        assert_eq!(stats.allocations, 3);
+        assert_eq!(stats.bytes, 13000);
+        assert_eq!(stats.peak, 8000);
+    }
+
+    #[test]
+    fn display() {
+        let stats = MemStats {
+            allocations: 12,
+            bytes: 3 << 20,
+            peak: 512,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "12 allocs, 3.0 MiB allocated, 512 B peak"
+        );
+    }
+}
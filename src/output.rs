@@ -0,0 +1,230 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::Answer;
+
+/// How the runner prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json, csv or markdown",
+                s
+            )),
+        }
+    }
+}
+
+/// One solved part, as emitted by the machine-readable formats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: u8,
+    pub answer: Answer,
+    /// The day's parse time, shared by both of its parts.
+    pub parse: Duration,
+    pub time: Duration,
+}
+
+impl Format {
+    /// Renders all records at once. `Text` is printed as the runner goes
+    /// and renders to nothing here.
+    pub fn render(&self, records: &[Record]) -> String {
+        match self {
+            Format::Text => String::new(),
+            Format::Json => json(records),
+            Format::Csv => csv(records),
+            Format::Markdown => markdown(records),
+        }
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// An answer as a JSON value: numbers stay numbers, no answer is `null`.
+pub fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(v) => v.to_string(),
+        Answer::UInt(v) => v.to_string(),
+        Answer::Text(s) => json_string(s),
+        Answer::None => "null".to_string(),
+    }
+}
+
+fn json(records: &[Record]) -> String {
+    let rows: Vec<String> = records
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"title\": {}, \"part\": {}, \
+                 \"answer\": {}, \"parse_ns\": {}, \"time_ns\": {}}}",
+                r.day,
+                json_string(r.title),
+                r.part,
+                json_answer(&r.answer),
+                r.parse.as_nanos(),
+                r.time.as_nanos()
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut out = String::from("day,title,part,answer,parse_ns,time_ns\n");
+    for r in records {
+        let answer = match &r.answer {
+            Answer::None => String::new(),
+            answer => answer.to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            r.day,
+            csv_field(r.title),
+            r.part,
+            csv_field(&answer),
+            r.parse.as_nanos(),
+            r.time.as_nanos()
+        );
+    }
+    out
+}
+
+fn markdown(records: &[Record]) -> String {
+    let mut out = String::from(concat!(
+        "| Day | Title | Parse | Part One | Time | Part Two | Time |\n",
+        "| --: | :---- | ----: | -------: | ---: | -------: | ---: |\n",
+    ));
+    for chunk in records.chunk_by(|a, b| a.day == b.day) {
+        let mut cells = vec![String::new(); 4];
+        for r in chunk {
+            let i = (r.part as usize - 1) * 2;
+            cells[i] = match &r.answer {
+                Answer::None => String::new(),
+                answer => format!("`{}`", answer),
+            };
+            cells[i + 1] = format!("{:.1?}", r.time);
+        }
+        let _ = writeln!(
+            out,
+            "| {} | {} | {:.1?} | {} |",
+            chunk[0].day,
+            chunk[0].title,
+            chunk[0].parse,
+            cells.join(" | ")
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 17,
+                title: "Chronospatial Computer",
+                part: 1,
+                answer: Answer::from("4,6,3"),
+                parse: Duration::from_micros(5),
+                time: Duration::from_micros(12),
+            },
+            Record {
+                day: 17,
+                title: "Chronospatial Computer",
+                part: 2,
+                answer: Answer::from(117440u64),
+                parse: Duration::from_micros(5),
+                time: Duration::from_micros(300),
+            },
+        ]
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn render_json() {
+        let out = Format::Json.render(&records());
+        assert!(out.contains(
+            "{\"day\": 17, \"title\": \"Chronospatial Computer\", \
+             \"part\": 1, \"answer\": \"4,6,3\", \"parse_ns\": 5000, \
+             \"time_ns\": 12000}"
+        ));
+        assert!(out.contains("\"answer\": 117440,"));
+        assert_eq!(json_answer(&Answer::None), "null");
+        assert_eq!(json_string("a\"b\n"), "\"a\\\"b\\n\"");
+    }
+
+    #[test]
+    fn render_csv() {
+        let out = Format::Csv.render(&records());
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[1],
+            "17,Chronospatial Computer,1,\"4,6,3\",5000,12000"
+        );
+        assert_eq!(
+            lines[2],
+            "17,Chronospatial Computer,2,117440,5000,300000"
+        );
+    }
+
+    #[test]
+    fn render_markdown() {
+        let out = Format::Markdown.render(&records());
+        assert_eq!(
+            out.lines().nth(2),
+            Some(
+                "| 17 | Chronospatial Computer | 5.0µs | `4,6,3` | 12.0µs \
+                 | `117440` | 300.0µs |"
+            )
+        );
+    }
+}
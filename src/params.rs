@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+/// Puzzle parameters that the statement gives outside the input, such as
+/// a grid size, with separate defaults for the example and the real
+/// input. Days without any use `()`.
+pub trait Params: Sized {
+    fn example() -> Self;
+    fn input() -> Self;
+
+    /// Overrides one parameter from a `--param key=value` argument.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// The defaults for an input variant with `overrides` applied.
+    fn resolve(
+        variant: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Self, String> {
+        let mut params = match variant {
+            "input" => Self::input(),
+            _ => Self::example(),
+        };
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    fn example() -> Self {}
+
+    fn input() -> Self {}
+
+    fn set(&mut self, key: &str, _: &str) -> Result<(), String> {
+        Err(unknown(key))
+    }
+}
+
+/// The error for a key a day does not have.
+pub fn unknown(key: &str) -> String {
+    format!("unknown parameter {:?}", key)
+}
+
+/// Parses a single parameter value.
+pub fn value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value {:?} for {}", value, key))
+}
+
+/// The error for a value that parses but is out of range.
+pub fn invalid(key: &str, value: &str, expected: &str) -> String {
+    format!(
+        "invalid value {:?} for {}, expected {}",
+        value, key, expected
+    )
+}
+
+/// Parses a parameter made of two values, such as `11x7` or `6,6`.
+pub fn pair<T: FromStr>(
+    key: &str,
+    value: &str,
+    sep: char,
+) -> Result<(T, T), String> {
+    let invalid = || {
+        format!(
+            "invalid value {:?} for {}, expected two values separated by {:?}",
+            value, key, sep
+        )
+    };
+    let (a, b) = value.split_once(sep).ok_or_else(invalid)?;
+    Ok((
+        a.parse().map_err(|_| invalid())?,
+        b.parse().map_err(|_| invalid())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers() {
+        assert_eq!(pair("grid", "11x7", 'x'), Ok((11, 7)));
+        assert_eq!(
+            pair::<i32>("grid", "11", 'x').unwrap_err(),
+            "invalid value \"11\" for grid, expected two values separated by 'x'"
+        );
+        assert_eq!(value("seconds", "100"), Ok(100));
+        assert_eq!(
+            invalid("seconds", "-1", "a count"),
+            "invalid value \"-1\" for seconds, expected a count"
+        );
+        assert!(value::<u8>("seconds", "-1").is_err());
+        assert_eq!(<()>::resolve("input", &[]), Ok(()));
+        let overrides = [("grid".to_string(), "1x1".to_string())];
+        assert_eq!(
+            <()>::resolve("example", &overrides).unwrap_err(),
+            "unknown parameter \"grid\""
+        );
+    }
+}
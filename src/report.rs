@@ -0,0 +1,69 @@
+use std::fs;
+use std::process::Command;
+
+/// Marks the start of the generated results in `README.md`.
+pub const BEGIN: &str = "<!-- results:begin -->";
+/// Marks the end of the generated results in `README.md`.
+pub const END: &str = "<!-- results:end -->";
+
+/// The README results section: the runner output in a fenced block,
+/// followed by the machine it was produced on.
+pub fn results_block(output: &str, cpu: Option<&str>) -> String {
+    format!(
+        "```text\n$ cargo run --release -- --time\n\n{}\n```\n\n`{}`\n",
+        output.trim_end(),
+        cpu.unwrap_or("unknown CPU")
+    )
+}
+
+/// Replaces everything between the [`BEGIN`] and [`END`] markers of
+/// `text` with `block`.
+pub fn splice(text: &str, block: &str) -> Result<String, String> {
+    let missing = |marker| format!("missing marker {}", marker);
+    let start = text.find(BEGIN).ok_or_else(|| missing(BEGIN))? + BEGIN.len();
+    let end = start + text[start..].find(END).ok_or_else(|| missing(END))?;
+    Ok(format!("{}\n{}{}", &text[..start], block, &text[end..]))
+}
+
+/// The CPU model as the OS reports it, if it can be found.
+pub fn cpu_model() -> Option<String> {
+    let model = match fs::read_to_string("/proc/cpuinfo") {
+        Ok(info) => info
+            .lines()
+            .find(|s| s.starts_with("model name"))?
+            .split_once(':')?
+            .1
+            .to_string(),
+        Err(_) => {
+            let output = Command::new("sysctl")
+                .args(["-n", "machdep.cpu.brand_string"])
+                .output()
+                .ok()?;
+            String::from_utf8(output.stdout).ok()?
+        }
+    };
+    let model = model.trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_block() {
+        let text = format!("# title\n\n{}\nold\n{}\n\nmore\n", BEGIN, END);
+        let block = results_block("--- Day 1 ---\n\n", Some("CPU"));
+        let spliced = splice(&text, &block).unwrap();
+        assert_eq!(
+            spliced,
+            format!(
+                "# title\n\n{}\n```text\n$ cargo run --release -- --time\n\n\
+                 --- Day 1 ---\n```\n\n`CPU`\n{}\n\nmore\n",
+                BEGIN, END
+            )
+        );
+        assert_eq!(splice(&spliced, &block).unwrap(), spliced);
+        assert!(splice("no markers", &block).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a search: the distance to every node it settled and,
+/// for each, all the predecessors it can be reached from on a shortest
+/// path, which together form the shortest-path DAG.
+///
+/// A search given a goal stops once no unsettled node can be as close as
+/// the nearest goal, so nodes farther away may be missing.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    dist: Vec<C>,
+    settled: Vec<bool>,
+    prev: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<N, C> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new() -> Self {
+        Paths {
+            index: HashMap::new(),
+            nodes: Vec::new(),
+            dist: Vec::new(),
+            settled: Vec::new(),
+            prev: Vec::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// The index of `node`, adding it at distance `dist` if it is new.
+    fn insert(&mut self, node: N, dist: C) -> (usize, bool) {
+        if let Some(&i) = self.index.get(&node) {
+            return (i, false);
+        }
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.dist.push(dist);
+        self.settled.push(false);
+        self.prev.push(Vec::new());
+        (i, true)
+    }
+
+    fn settled(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied().filter(|&i| self.settled[i])
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.settled(node).map(|i| self.dist[i])
+    }
+
+    /// Every settled node with its distance, in the order they were
+    /// settled.
+    pub fn distances(&self) -> impl Iterator<Item = (&N, C)> {
+        (0..self.nodes.len())
+            .filter(|&i| self.settled[i])
+            .map(|i| (&self.nodes[i], self.dist[i]))
+    }
+
+    /// The nodes right before `node` on its shortest paths.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let prev = match self.settled(node) {
+            Some(i) => &self.prev[i][..],
+            None => &[],
+        };
+        prev.iter().map(|&i| &self.nodes[i])
+    }
+
+    /// The goals at the shortest distance found, in the order they were
+    /// settled.
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(|&i| &self.nodes[i])
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goals.first().map(|&i| self.dist[i])
+    }
+
+    /// One shortest path from a start to `to`, both included.
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        let mut i = self.settled(to)?;
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(&j) = self.prev[i].first() {
+            path.push(self.nodes[j].clone());
+            i = j;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from a start to `to`. There can be
+    /// exponentially many.
+    pub fn all_paths(&self, to: &N) -> Vec<Vec<N>> {
+        fn walk<N: Clone, C>(
+            paths: &Paths<N, C>,
+            i: usize,
+            suffix: &mut Vec<N>,
+            out: &mut Vec<Vec<N>>,
+        ) {
+            suffix.push(paths.nodes[i].clone());
+            if paths.prev[i].is_empty() {
+                out.push(suffix.iter().rev().cloned().collect());
+            }
+            for &j in &paths.prev[i] {
+                walk(paths, j, suffix, out);
+            }
+            suffix.pop();
+        }
+        let mut out = Vec::new();
+        if let Some(i) = self.settled(to) {
+            walk(self, i, &mut Vec::new(), &mut out);
+        }
+        out
+    }
+
+    /// The nodes on any shortest path to any of `targets`.
+    pub fn on_paths<'a>(
+        &self,
+        targets: impl IntoIterator<Item = &'a N>,
+    ) -> HashSet<N>
+    where
+        N: 'a,
+    {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = targets
+            .into_iter()
+            .filter_map(|n| self.settled(n))
+            .collect();
+        while let Some(i) = stack.pop() {
+            if !seen[i] {
+                seen[i] = true;
+                stack.extend(&self.prev[i]);
+            }
+        }
+        (0..self.nodes.len())
+            .filter(|&i| seen[i])
+            .map(|i| self.nodes[i].clone())
+            .collect()
+    }
+}
+
+/// Breadth-first search from `starts` over unit-weight edges, stopping
+/// once the nearest node satisfying `goal` and all its shortest paths are
+/// known.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let (i, new) = paths.insert(start, 0);
+        if new {
+            paths.settled[i] = true;
+            queue.push_back(i);
+        }
+    }
+    while let Some(i) = queue.pop_front() {
+        let d = paths.dist[i];
+        if paths.goal_distance().is_some_and(|best| d > best) {
+            break;
+        }
+        if goal(&paths.nodes[i]) {
+            paths.goals.push(i);
+        }
+        if !paths.goals.is_empty() {
+            continue;
+        }
+        for next in neighbours(&paths.nodes[i].clone()) {
+            let (j, new) = paths.insert(next, d + 1);
+            if new {
+                paths.settled[j] = true;
+                queue.push_back(j);
+            }
+            if paths.dist[j] == d + 1 {
+                paths.prev[j].push(i);
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm from `starts`, where `neighbours` gives each
+/// neighbour with the cost of the edge to it. Stops like [`bfs`].
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), goal)
+}
+
+/// A* search: [`dijkstra`] guided by `heuristic`, which must never
+/// overestimate the remaining cost to a goal nor drop by more than the
+/// cost of an edge.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let (i, new) = paths.insert(start, C::default());
+        if new {
+            let h = heuristic(&paths.nodes[i]);
+            queue.push(Reverse((h, C::default(), i)));
+        }
+    }
+    while let Some(Reverse((f, d, i))) = queue.pop() {
+        if paths.settled[i] || d > paths.dist[i] {
+            continue;
+        }
+        if paths.goal_distance().is_some_and(|best| f > best) {
+            break;
+        }
+        paths.settled[i] = true;
+        if goal(&paths.nodes[i]) {
+            paths.goals.push(i);
+        }
+        for (next, cost) in neighbours(&paths.nodes[i].clone()) {
+            let nd = d + cost;
+            let (j, new) = paths.insert(next, nd);
+            if paths.settled[j] {
+                // A predecessor on a tie can be settled after the node.
+                if nd == paths.dist[j] && j != i {
+                    paths.prev[j].push(i);
+                }
+                continue;
+            }
+            if new || nd < paths.dist[j] {
+                paths.dist[j] = nd;
+                paths.prev[j] = vec![i];
+                let h = heuristic(&paths.nodes[j]);
+                queue.push(Reverse((nd + h, nd, j)));
+            } else if nd == paths.dist[j] {
+                paths.prev[j].push(i);
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 grid of points without the centre.
+    fn ring(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..3).contains(&x) && (0..3).contains(&y))
+            .filter(|&p| p != (1, 1))
+            .collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let paths = bfs([(0, 0)], ring, |_| false);
+        assert_eq!(paths.goal_distance(), None);
+        assert_eq!(paths.distances().count(), 8);
+        assert_eq!(paths.distance(&(2, 2)), Some(4));
+        assert_eq!(paths.distance(&(1, 1)), None);
+        assert_eq!(paths.all_paths(&(2, 2)).len(), 2);
+        assert_eq!(paths.on_paths([&(2, 2)]).len(), 8);
+        let path = paths.path(&(2, 0)).unwrap();
+        assert_eq!(path, [(0, 0), (1, 0), (2, 0)]);
+
+        let paths = bfs([(0, 0)], ring, |&p| p == (2, 1));
+        assert_eq!(paths.goal_distance(), Some(3));
+        assert_eq!(paths.goals().collect::<Vec<_>>(), [&(2, 1)]);
+        assert_eq!(paths.distance(&(2, 2)), None);
+    }
+
+    #[test]
+    fn weighted() {
+        // Moving sideways is expensive along the top row.
+        let weighted = |&p: &(i32, i32)| {
+            ring(&p).into_iter().map(move |q| {
+                let cost = match (q.0 == p.0, p.1) {
+                    (true, _) => 1,
+                    (false, 0) => 10,
+                    (false, _) => 2,
+                };
+                (q, cost)
+            })
+        };
+        let end = (2, 2);
+        let paths = dijkstra([(0, 0)], weighted, |&p| p == end);
+        assert_eq!(paths.goal_distance(), Some(6));
+        assert_eq!(
+            paths.path(&end).unwrap(),
+            [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]
+        );
+        assert_eq!(paths.predecessors(&end).collect::<Vec<_>>(), [&(1, 2)]);
+
+        let manhattan =
+            |p: &(i32, i32)| (end.0 - p.0).abs() + (end.1 - p.1).abs();
+        let guided = astar([(0, 0)], weighted, manhattan, |&p| p == end);
+        assert_eq!(guided.goal_distance(), Some(6));
+        assert_eq!(guided.path(&end), paths.path(&end));
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+/// The days and parts picked on the command line.
+///
+/// Each spec is `all`, a day `6`, a range `10-15`, or either of the last
+/// two restricted to one part with a suffix, as in `6.2` or `10-15.1`.
+/// Days are always visited in calendar order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Selection {
+    parts: BTreeMap<u8, [bool; 2]>,
+}
+
+impl Selection {
+    /// Every part of every day in the registry.
+    pub fn all() -> Self {
+        let mut selection = Selection::default();
+        for puzzle in crate::puzzles() {
+            selection.parts.insert(puzzle.day(), [true; 2]);
+        }
+        selection
+    }
+
+    pub fn add(&mut self, spec: &str) -> Result<(), String> {
+        if spec == "all" {
+            self.parts.extend(Selection::all().parts);
+            return Ok(());
+        }
+        let (days, part) = match spec.split_once('.') {
+            Some((days, part)) => match part {
+                "1" => (days, [true, false]),
+                "2" => (days, [false, true]),
+                _ => {
+                    return Err(format!(
+                        "invalid part in {:?}, expected 1 or 2",
+                        spec
+                    ))
+                }
+            },
+            None => (spec, [true; 2]),
+        };
+        let (first, last) = days.split_once('-').unwrap_or((days, days));
+        let (first, last) = (day(spec, first)?, day(spec, last)?);
+        if first > last {
+            return Err(format!("empty day range {:?}", spec));
+        }
+        for day in first..=last {
+            let parts = self.parts.entry(day).or_default();
+            parts[0] |= part[0];
+            parts[1] |= part[1];
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.parts.keys().copied()
+    }
+
+    pub fn contains(&self, day: u8, part: u8) -> bool {
+        let Some(parts) = self.parts.get(&day) else {
+            return false;
+        };
+        let index = (part as usize).checked_sub(1);
+        index.and_then(|i| parts.get(i)).copied().unwrap_or(false)
+    }
+}
+
+fn day(spec: &str, s: &str) -> Result<u8, String> {
+    let last = crate::puzzles().len();
+    match s.parse() {
+        Ok(day) if crate::puzzle(day).is_some() => Ok(day),
+        _ => Err(format!(
+            "no such day {:?} in {:?}, expected 1-{} or all",
+            s, spec, last
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(specs: &[&str]) -> Result<Selection, String> {
+        let mut selection = Selection::default();
+        for spec in specs {
+            selection.add(spec)?;
+        }
+        Ok(selection)
+    }
+
+    #[test]
+    fn specs() {
+        let selection = select(&["12-14", "6.2", "13.1"]).unwrap();
+        let days: Vec<u8> = selection.days().collect();
+        assert_eq!(days, [6, 12, 13, 14]);
+        assert!(!selection.contains(6, 1));
+        assert!(selection.contains(6, 2));
+        assert!(selection.contains(13, 1) && selection.contains(13, 2));
+        assert!(!selection.contains(7, 1));
+
+        let selection = select(&["10-15.1"]).unwrap();
+        assert!(selection.contains(15, 1) && !selection.contains(15, 2));
+
+        assert_eq!(select(&["all"]).unwrap().days().count(), 25);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = select(&["26"]).unwrap_err();
+        assert_eq!(err, "no such day \"26\" in \"26\", expected 1-25 or all");
+        assert!(select(&["0"]).is_err());
+        assert!(select(&["6.3"]).is_err());
+        assert!(select(&["15-10"]).is_err());
+        assert!(select(&["x-3"]).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::output::json_answer;
+use crate::output::json_string;
+use crate::supervise;
+use crate::Outcome;
+use crate::ParseError;
+
+/// The largest request body accepted, well above any puzzle input.
+const MAX_BODY: usize = 16 << 20;
+
+/// An HTTP response: status code and JSON body.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\": {}}}", json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            500 => "Internal Server Error",
+            504 => "Gateway Timeout",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// Answers one request. `POST /day/{n}/part/{p}` solves the body as the
+/// puzzle input, with query parameters as `--param` overrides, and
+/// `GET /days` lists the registered days.
+pub fn handle(
+    method: &str,
+    target: &str,
+    body: &str,
+    timeout: Option<Duration>,
+) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, &segments[..]) {
+        ("GET", ["days"]) => Response::ok(days()),
+        ("POST", ["day", day, "part", part]) => {
+            solve(day, part, query, body.to_string(), timeout)
+        }
+        (_, ["days"]) | (_, ["day", _, "part", _]) => {
+            Response::error(405, "method not allowed")
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn days() -> String {
+    let days: Vec<String> = crate::puzzles()
+        .iter()
+        .map(|p| {
+            format!(
+                "{{\"day\": {}, \"title\": {}}}",
+                p.day(),
+                json_string(p.title())
+            )
+        })
+        .collect();
+    format!("[{}]", days.join(", "))
+}
+
+fn solve(
+    day: &str,
+    part: &str,
+    query: &str,
+    input: String,
+    timeout: Option<Duration>,
+) -> Response {
+    let Some(puzzle) = day.parse().ok().and_then(crate::puzzle) else {
+        return Response::error(404, &format!("no such day {:?}", day));
+    };
+    let part: u8 = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => {
+            return Response::error(404, &format!("no such part {:?}", part))
+        }
+    };
+    let overrides: Vec<(String, String)> = query
+        .split('&')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (key, value) = s.split_once('=').unwrap_or((s, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+    let settings = match puzzle.settings("input", &overrides) {
+        Ok(settings) => settings,
+        Err(e) => return Response::error(400, &e),
+    };
+
+    let outcome = supervise(timeout, move || {
+        let t0 = Instant::now();
+        let parsed = puzzle.parse(&input)?;
+        let parse = t0.elapsed();
+        let t0 = Instant::now();
+        let answer = puzzle.solve(&parsed, &settings, part);
+        Ok::<_, ParseError>((answer, parse, t0.elapsed()))
+    });
+    match outcome {
+        Outcome::Done {
+            value: Ok((answer, parse, time)),
+            ..
+        } => Response::ok(format!(
+            "{{\"day\": {}, \"part\": {}, \"answer\": {}, \
+             \"parse_ns\": {}, \"time_ns\": {}}}",
+            puzzle.day(),
+            part,
+            json_answer(&answer),
+            parse.as_nanos(),
+            time.as_nanos()
+        )),
+        Outcome::Done { value: Err(e), .. } => {
+            Response::error(400, &e.to_string())
+        }
+        Outcome::Failed { .. } => Response::error(500, &outcome.to_string()),
+        Outcome::TimedOut { .. } => {
+            Response::error(504, &outcome.to_string())
+        }
+    }
+}
+
+/// Reads one request, answers it and closes the connection.
+fn connection(
+    stream: TcpStream,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut request = line.split_whitespace();
+    let (method, target) = match (request.next(), request.next()) {
+        (Some(method), Some(target)) => {
+            (method.to_string(), target.to_string())
+        }
+        _ => return respond(&stream, &Response::error(400, "bad request")),
+    };
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return respond(&stream, &Response::error(413, "input too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let response = match String::from_utf8(body) {
+        Ok(body) => handle(&method, &target, &body, timeout),
+        Err(_) => Response::error(400, "input is not UTF-8"),
+    };
+    respond(&stream, &response)
+}
+
+fn respond(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )
+}
+
+/// Serves requests from `listener`, one thread per connection, until the
+/// listener fails.
+pub fn serve(listener: TcpListener, timeout: Option<Duration>) {
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || connection(stream, timeout));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_example;
+
+    #[test]
+    fn routes() {
+        let response =
+            handle("POST", "/day/1/part/2", &read_example(1), None);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .starts_with("{\"day\": 1, \"part\": 2, \"answer\": 31,"));
+
+        let response = handle("GET", "/days", "", None);
+        assert!(response
+            .body
+            .contains("{\"day\": 6, \"title\": \"Guard Gallivant\"}"));
+
+        assert_eq!(handle("POST", "/day/26/part/1", "", None).status, 404);
+        assert_eq!(handle("POST", "/day/1/part/3", "", None).status, 404);
+        assert_eq!(handle("GET", "/day/1/part/1", "", None).status, 405);
+        assert_eq!(handle("POST", "/day/1/part/1", "x y", None).status, 400);
+        let response = handle("POST", "/day/1/part/1?grid=1x1", "1 2", None);
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn params() {
+        let input = read_example(14);
+        let response =
+            handle("POST", "/day/14/part/1?grid=11x7", &input, None);
+        assert!(response.body.contains("\"answer\": 12,"));
+    }
+
+    #[test]
+    fn http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, None));
+
+        let input = read_example(1);
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\n\
+             Content-Length: {}\r\n\r\n{}",
+            input.len(),
+            input
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\": 11,"));
+    }
+}
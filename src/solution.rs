@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::iter;
+
+use crate::Answer;
+use crate::Frame;
+use crate::Image;
+use crate::Params;
+use crate::ParseError;
+
+/// One day of the calendar: its number, title and the two parts.
+///
+/// `parse` is the optional parse step. A day that has no separate parse
+/// phase sets `Input` to `String` and hands the raw text to its parts.
+/// `Params` holds what the puzzle statement gives besides the input.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input;
+    type Params: Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input, params: &Self::Params) -> Answer;
+    fn part_two(input: &Self::Input, params: &Self::Params) -> Answer;
+
+    /// Pictures of the solution at work for `aoc viz`, produced lazily.
+    /// Most days have none.
+    fn frames<'a>(
+        _input: &'a Self::Input,
+        _params: &'a Self::Params,
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        Box::new(iter::empty())
+    }
+
+    /// A picture of the solved state for `aoc image`, if the day has one.
+    fn image(_input: &Self::Input, _params: &Self::Params) -> Option<Image> {
+        None
+    }
+}
+
+/// A value of some day's type with the type erased, checked against the
+/// day on the way back out.
+struct Erased {
+    day: u8,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl Erased {
+    fn get<T: 'static>(&self, day: u8) -> &T {
+        assert_eq!(self.day, day, "value belongs to another day");
+        self.value.downcast_ref().unwrap()
+    }
+}
+
+/// A day's parsed input, produced by [`Puzzle::parse`] and shared by both
+/// parts.
+pub struct Parsed(Erased);
+
+impl Parsed {
+    pub fn day(&self) -> u8 {
+        self.0.day
+    }
+}
+
+/// A day's resolved parameters, produced by [`Puzzle::settings`].
+pub struct Settings(Erased);
+
+impl Settings {
+    pub fn day(&self) -> u8 {
+        self.0.day
+    }
+}
+
+/// Object-safe view of a [`Solution`], as handed out by the registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    /// The parameter defaults for `variant` with `overrides` applied.
+    fn settings(
+        &self,
+        variant: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Settings, String>;
+
+    /// Solves one part of input from [`Puzzle::parse`] on the same day.
+    ///
+    /// # Panics
+    ///
+    /// If `parsed` or `settings` belongs to another day.
+    fn part_one(&self, parsed: &Parsed, settings: &Settings) -> Answer;
+    fn part_two(&self, parsed: &Parsed, settings: &Settings) -> Answer;
+
+    /// Solves part 1 or 2.
+    ///
+    /// # Panics
+    ///
+    /// On a part other than 1 or 2, or if `parsed` or `settings` belongs
+    /// to another day.
+    fn solve(
+        &self,
+        parsed: &Parsed,
+        settings: &Settings,
+        part: u8,
+    ) -> Answer {
+        match part {
+            1 => self.part_one(parsed, settings),
+            2 => self.part_two(parsed, settings),
+            _ => panic!("day {} has no part {}", self.day(), part),
+        }
+    }
+
+    /// The day's [`Solution::frames`], empty if it has none.
+    fn frames<'a>(
+        &self,
+        parsed: &'a Parsed,
+        settings: &'a Settings,
+    ) -> Box<dyn Iterator<Item = Frame> + 'a>;
+
+    /// The day's [`Solution::image`].
+    fn image(&self, parsed: &Parsed, settings: &Settings) -> Option<Image>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync,
+    S::Input: Send + Sync + 'static,
+    S::Params: Send + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Parsed(Erased {
+            day: S::DAY,
+            value: Box::new(S::parse(input)?),
+        }))
+    }
+
+    fn settings(
+        &self,
+        variant: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Settings, String> {
+        Ok(Settings(Erased {
+            day: S::DAY,
+            value: Box::new(S::Params::resolve(variant, overrides)?),
+        }))
+    }
+
+    fn part_one(&self, parsed: &Parsed, settings: &Settings) -> Answer {
+        S::part_one(parsed.0.get(S::DAY), settings.0.get(S::DAY))
+    }
+
+    fn part_two(&self, parsed: &Parsed, settings: &Settings) -> Answer {
+        S::part_two(parsed.0.get(S::DAY), settings.0.get(S::DAY))
+    }
+
+    fn frames<'a>(
+        &self,
+        parsed: &'a Parsed,
+        settings: &'a Settings,
+    ) -> Box<dyn Iterator<Item = Frame> + 'a> {
+        S::frames(parsed.0.get(S::DAY), settings.0.get(S::DAY))
+    }
+
+    fn image(&self, parsed: &Parsed, settings: &Settings) -> Option<Image> {
+        S::image(parsed.0.get(S::DAY), settings.0.get(S::DAY))
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::Grid;
+
+/// A terminal colour for one cell of a [`Frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Default,
+    Gray,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Default => "\x1b[0m",
+            Color::Gray => "\x1b[90m",
+            Color::Red => "\x1b[1;31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+        }
+    }
+}
+
+/// One picture of a day's state: a caption above a grid of coloured
+/// characters.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<(char, Color)>,
+}
+
+impl Frame {
+    /// Colours each character of `grid` with `palette`.
+    pub fn new(
+        caption: impl Into<String>,
+        grid: &Grid<char>,
+        palette: impl Fn(char) -> Color,
+    ) -> Self {
+        Frame {
+            caption: caption.into(),
+            cells: grid.map(|&c| (c, palette(c))),
+        }
+    }
+
+    /// The frame with ANSI colour escapes, one line per row.
+    pub fn ansi(&self) -> String {
+        let mut out = format!("{}\n", self.caption);
+        for row in self.cells.rows() {
+            let mut color = Color::Default;
+            for &(c, next) in row {
+                if next != color {
+                    out += next.ansi();
+                    color = next;
+                }
+                out.push(c);
+            }
+            if color != Color::Default {
+                out += Color::Default.ansi();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The frame without colours.
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.caption)?;
+        write!(f, "{}", self.cells.map(|&(c, _)| c))
+    }
+}
+
+/// Draws each frame over the previous one, waiting `delay` in between or,
+/// with `step`, until Enter is pressed.
+pub fn play(
+    frames: impl Iterator<Item = Frame>,
+    delay: Duration,
+    step: bool,
+) -> io::Result<usize> {
+    let mut stdout = io::stdout().lock();
+    let mut stdin = io::stdin().lock();
+    let mut count = 0;
+    for frame in frames {
+        if count > 0 {
+            match step {
+                true => stdin.read_line(&mut String::new()).map(|_| ())?,
+                false => thread::sleep(delay),
+            }
+        }
+        // Home the cursor and clear the screen.
+        write!(stdout, "\x1b[H\x1b[2J{}", frame.ansi())?;
+        stdout.flush()?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Writes each frame with its colours to `dir` as `00000.txt`,
+/// `00001.txt` and so on, for viewing with `cat` or `less -R`. The
+/// directory is only created once there is a frame.
+pub fn dump(
+    frames: impl Iterator<Item = Frame>,
+    dir: &Path,
+) -> io::Result<usize> {
+    let mut count = 0;
+    for frame in frames {
+        if count == 0 {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(dir.join(format!("{:05}.txt", count)), frame.ansi())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::Source;
+
+    fn frame() -> Frame {
+        let text = "#.@\n";
+        let grid = Grid::parse(&Source::new(1, text), text, Some).unwrap();
+        Frame::new("step 1", &grid, |c| match c {
+            '#' => Color::Gray,
+            '@' => Color::Red,
+            _ => Color::Default,
+        })
+    }
+
+    #[test]
+    fn render() {
+        let frame = frame();
+        assert_eq!(frame.to_string(), "step 1\n#.@\n");
+        assert_eq!(
+            frame.ansi(),
+            "step 1\n\x1b[90m#\x1b[0m.\x1b[1;31m@\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn dump_frames() {
+        let dir = env::temp_dir().join(format!("aoc-viz-{}", process::id()));
+        let count = dump([frame(), frame()].into_iter(), &dir).unwrap();
+        assert_eq!(count, 2);
+        let text = fs::read_to_string(dir.join("00001.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(text, frame().ansi());
+    }
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::Record;
+
+/// Polls the modification times of a set of files.
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let time = modified(&path);
+                (path, time)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.iter().map(|(path, _)| path)
+    }
+
+    /// Whether any file was modified, created or removed since the last
+    /// call.
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for (path, time) in self.files.iter_mut() {
+            let now = modified(path);
+            if now != *time {
+                *time = now;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// One line per answer that differs from the previous run, such as
+/// `day 15 part 1: 10092 -> 2028`.
+pub fn diff(previous: &[Record], current: &[Record]) -> Vec<String> {
+    current
+        .iter()
+        .filter_map(|r| {
+            let old = previous
+                .iter()
+                .find(|p| (p.day, p.part) == (r.day, r.part))?;
+            (old.answer != r.answer).then(|| {
+                format!(
+                    "day {} part {}: {} -> {}",
+                    r.day, r.part, old.answer, r.answer
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::File;
+    use std::process;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Answer;
+
+    #[test]
+    fn changed() {
+        let path = env::temp_dir()
+            .join(format!("aoc-watch-test-{}.txt", process::id()));
+        fs::write(&path, "1").unwrap();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(!watcher.changed());
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|f| f.set_modified(later))
+            .unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn answers() {
+        let record = |part, answer: u64| Record {
+            day: 15,
+            title: "Warehouse Woes",
+            part,
+            answer: Answer::from(answer),
+            parse: Duration::ZERO,
+            time: Duration::ZERO,
+        };
+        let previous = [record(1, 10092), record(2, 9021)];
+        let current = [record(1, 2028), record(2, 9021)];
+        assert_eq!(
+            diff(&previous, &current),
+            ["day 15 part 1: 10092 -> 2028"]
+        );
+        assert!(diff(&[], &current).is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Name of the threads [`supervise`] runs work on, so a panic hook can
+/// tell their panics apart from the runner's own.
+pub const SOLVER_THREAD: &str = "solver";
+
+/// How a supervised piece of work ended.
+#[derive(Debug, PartialEq)]
+pub enum Outcome<T> {
+    Done { value: T, time: Duration },
+    Failed { message: String },
+    TimedOut { after: Duration },
+}
+
+impl<T> fmt::Display for Outcome<T> {
+    /// Describes the outcomes that did not produce a value.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Done { time, .. } => write!(f, "done in {:?}", time),
+            Outcome::Failed { message } => write!(f, "FAILED ({})", message),
+            Outcome::TimedOut { after } => {
+                write!(f, "TIMEOUT (after {:?})", after)
+            }
+        }
+    }
+}
+
+/// Runs `f` on its own thread, catching a panic and giving up after
+/// `timeout`. Work that times out cannot be stopped and is left running
+/// in the background.
+pub fn supervise<T, F>(timeout: Option<Duration>, f: F) -> Outcome<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(SOLVER_THREAD.to_string())
+        .spawn(move || {
+            let t0 = Instant::now();
+            let value = f();
+            let _ = tx.send(t0.elapsed());
+            value
+        });
+    let handle = match spawned {
+        Ok(handle) => handle,
+        Err(e) => {
+            return Outcome::Failed {
+                message: e.to_string(),
+            }
+        }
+    };
+
+    let finished = match timeout {
+        Some(after) => rx.recv_timeout(after).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Some(after),
+            mpsc::RecvTimeoutError::Disconnected => None,
+        }),
+        None => rx.recv().map_err(|_| None),
+    };
+    if let Err(Some(after)) = finished {
+        // Dropping the handle detaches the thread instead of waiting.
+        drop(handle);
+        return Outcome::TimedOut { after };
+    }
+    match (finished, handle.join()) {
+        (Ok(time), Ok(value)) => Outcome::Done { value, time },
+        (_, Err(payload)) => Outcome::Failed {
+            message: panic_message(payload),
+        },
+        (Err(_), Ok(_)) => unreachable!("solver exited without a result"),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn done() {
+        let outcome = supervise(None, || 6 * 7);
+        assert!(matches!(outcome, Outcome::Done { value: 42, .. }));
+    }
+
+    #[test]
+    fn failed() {
+        let outcome: Outcome<()> = supervise(None, || panic!("no {}", "S"));
+        assert_eq!(
+            outcome,
+            Outcome::Failed {
+                message: "no S".to_string()
+            }
+        );
+        assert_eq!(outcome.to_string(), "FAILED (no S)");
+    }
+
+    #[test]
+    fn timed_out() {
+        let after = Duration::from_millis(10);
+        let t0 = Instant::now();
+        let outcome = supervise(Some(after), || {
+            thread::sleep(Duration::from_secs(1));
+        });
+        assert_eq!(outcome, Outcome::TimedOut { after });
+        // The sleeping thread is left behind rather than waited for.
+        assert!(t0.elapsed() < Duration::from_millis(500));
+    }
+}
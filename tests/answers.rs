@@ -0,0 +1,89 @@
+//! Solves every input file under `inputs/` and compares the answers with
+//! `inputs/answers.txt`. A new case is a new `NN-example-<name>.txt` plus
+//! its line in the manifest.
+
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+use aoc::Answers;
+use aoc::Check;
+use aoc::InputResolver;
+use aoc::Puzzle;
+
+/// Checks the recorded parts of one day and variant, returning a line per
+/// problem.
+fn check(
+    resolver: &InputResolver,
+    answers: &Answers,
+    puzzle: &dyn Puzzle,
+    variant: &str,
+) -> Vec<String> {
+    let day = puzzle.day();
+    let case = format!("day {} {}", day, variant);
+    if !answers.contains(day, variant) {
+        return vec![format!("{}: not in {}", case, Answers::FILENAME)];
+    }
+    let input = match resolver.read(day, variant) {
+        Ok(input) => input,
+        Err(e) => return vec![format!("{}: {}", case, e)],
+    };
+    let parsed = match puzzle.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => return vec![format!("{}: {}", case, e)],
+    };
+    let settings = puzzle.settings(variant, &[]).unwrap();
+
+    let mut problems = Vec::new();
+    for part in [1, 2] {
+        if answers.expected(day, variant, part).is_none() {
+            continue;
+        }
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+            puzzle.solve(&parsed, &settings, part)
+        }));
+        match solved {
+            Ok(answer) => match answers.check(day, variant, part, &answer) {
+                Check::Pass => {}
+                check => problems.push(format!(
+                    "{} part {}: {} {}",
+                    case, part, answer, check
+                )),
+            },
+            Err(_) => {
+                problems.push(format!("{} part {}: panicked", case, part))
+            }
+        }
+    }
+    problems
+}
+
+fn check_all(variants: impl Fn(&InputResolver, u8) -> Vec<String>) {
+    let resolver = InputResolver::new(None);
+    let answers = Answers::load(&resolver).unwrap();
+    let mut cases = 0;
+    let mut problems = Vec::new();
+    for &puzzle in aoc::puzzles() {
+        for variant in variants(&resolver, puzzle.day()) {
+            cases += 1;
+            problems.extend(check(&resolver, &answers, puzzle, &variant));
+        }
+    }
+    assert!(cases > 0, "no input files found");
+    assert!(problems.is_empty(), "\n{}\n", problems.join("\n"));
+}
+
+#[test]
+fn examples() {
+    check_all(|resolver, day| resolver.examples(day));
+}
+
+/// The real inputs take close to a minute in a debug build, run them with
+/// `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn inputs() {
+    check_all(|resolver, day| match resolver.find(day, "input") {
+        Ok(_) => vec!["input".to_string()],
+        Err(_) => vec![],
+    });
+}